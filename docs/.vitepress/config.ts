@@ -46,8 +46,13 @@ export default defineConfig({
             link: '/dev-tools/backends/',
             items: [
               { text: 'asdf', link: '/dev-tools/backends/asdf' },
+              { text: 'aqua', link: '/dev-tools/backends/aqua' },
               { text: 'cargo', link: '/dev-tools/backends/cargo' },
+              { text: 'conda', link: '/dev-tools/backends/conda' },
+              { text: 'dotnet', link: '/dev-tools/backends/dotnet' },
+              { text: 'gem', link: '/dev-tools/backends/gem' },
               { text: 'go', link: '/dev-tools/backends/go' },
+              { text: 'http', link: '/dev-tools/backends/http' },
               { text: 'npm', link: '/dev-tools/backends/npm' },
               { text: 'pipx', link: '/dev-tools/backends/pipx' },
               { text: 'spm', link: '/dev-tools/backends/spm' },
@@ -0,0 +1,608 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use eyre::{bail, eyre, Result};
+use flate2::read::GzDecoder;
+use serde_derive::Deserialize;
+use versions::Versioning;
+
+use crate::backend::{Backend, BackendType};
+use crate::cache::CacheManager;
+use crate::cli::args::BackendArg;
+use crate::cli::version::{ARCH, OS};
+use crate::config::Settings;
+use crate::file::display_path;
+use crate::git::Git;
+use crate::github::GithubRelease;
+use crate::http::HTTP;
+use crate::install_context::InstallContext;
+use crate::toolset::ToolVersion;
+use crate::{dirs, download_cache, file, github, hash, http, lockfile};
+
+/// installs tools using package definitions from the aqua registry
+/// https://github.com/aquaproj/aqua-registry
+#[derive(Debug)]
+pub struct AquaBackend {
+    ba: BackendArg,
+    remote_version_cache: CacheManager<Vec<String>>,
+}
+
+impl Backend for AquaBackend {
+    fn get_type(&self) -> BackendType {
+        BackendType::Aqua
+    }
+
+    fn fa(&self) -> &BackendArg {
+        &self.ba
+    }
+
+    fn _list_remote_versions(&self) -> Result<Vec<String>> {
+        self.remote_version_cache
+            .get_or_try_init(|| {
+                let pkg = self.package()?;
+                let prefix = pkg.version_prefix.clone().unwrap_or_default();
+                Ok(github::list_releases(&pkg.repo())?
+                    .into_iter()
+                    .filter_map(|r| r.tag_name.strip_prefix(&prefix).map(|v| v.to_string()))
+                    .map(|v| v.trim_start_matches('v').to_string())
+                    .rev()
+                    .collect())
+            })
+            .cloned()
+    }
+
+    fn install_version_impl(&self, ctx: &InstallContext) -> Result<()> {
+        let settings = Settings::get();
+        settings.ensure_experimental("aqua backend")?;
+        let pkg = self.package()?;
+        let release = self.get_release(&pkg, &ctx.tv.version)?;
+        let tag = release.tag_name.clone();
+        let pkg = pkg.for_version(&tag).for_platform(goos(), goarch());
+        let mut tmpl = pkg.template_vars(&tag);
+
+        let (url, asset) = match pkg.r#type.as_deref() {
+            Some("github_release") => {
+                let asset = render(pkg.asset.as_deref().unwrap_or_default(), &tmpl)?;
+                let url = release
+                    .assets
+                    .iter()
+                    .find(|a| a.name == asset)
+                    .map(|a| a.browser_download_url.clone())
+                    .ok_or_else(|| eyre!("asset {asset} not found in {} {tag}", pkg.repo()))?;
+                (url, asset)
+            }
+            Some("http") => {
+                let url = render(pkg.url.as_deref().unwrap_or_default(), &tmpl)?;
+                let asset = url.rsplit('/').next().unwrap().to_string();
+                (url, asset)
+            }
+            t => bail!(
+                "aqua package type {} is not supported",
+                t.unwrap_or("(none)")
+            ),
+        };
+        tmpl.insert("Asset", asset.clone());
+        tmpl.insert(
+            "AssetWithoutExt",
+            asset_without_ext(&asset, &pkg.format()).to_string(),
+        );
+
+        let archive = ctx.tv.download_path().join(&asset);
+        ctx.pr.set_message(format!("downloading {asset}"));
+        download_cache::download(&url, &archive, Some(ctx.pr.as_ref()), |archive| {
+            if let Some(checksum) = self.fetch_checksum(&pkg, &release, &tmpl)? {
+                ctx.pr.set_message(format!("verifying {asset}"));
+                hash::ensure_checksum_sha256(archive, &checksum, Some(ctx.pr.as_ref()))?;
+            }
+            match pkg.r#type.as_deref() {
+                Some("github_release") => {
+                    github::verify_cosign_bundle(self.id(), &release, &asset, archive)
+                }
+                _ => hash::verify_signature(self.id(), archive, None),
+            }
+        })?;
+        lockfile::record_download(&ctx.tv, &url, &archive)?;
+
+        ctx.pr.set_message(format!("installing {asset}"));
+        self.install(&ctx.tv, &pkg, &archive, &tmpl)
+    }
+}
+
+impl AquaBackend {
+    pub fn from_arg(ba: BackendArg) -> Self {
+        Self {
+            remote_version_cache: CacheManager::new(
+                ba.cache_path.join("remote_versions-$KEY.msgpack.z"),
+            ),
+            ba,
+        }
+    }
+
+    fn package(&self) -> Result<AquaPackage> {
+        let path = registry_dir()?
+            .join("pkgs")
+            .join(self.name())
+            .join("registry.yaml");
+        if !path.exists() {
+            bail!(
+                "{} is not in the aqua registry: {} does not exist",
+                self.name(),
+                display_path(&path)
+            );
+        }
+        let registry: AquaRegistry = serde_yaml::from_str(&file::read_to_string(&path)?)?;
+        let mut packages = registry.packages.into_iter();
+        let first = packages.next();
+        packages
+            .find(|p| p.name.as_deref() == Some(self.name()))
+            .or(first)
+            .ok_or_else(|| eyre!("no packages found in {}", display_path(&path)))
+    }
+
+    fn get_release(&self, pkg: &AquaPackage, version: &str) -> Result<GithubRelease> {
+        let prefix = pkg.version_prefix.clone().unwrap_or_default();
+        let repo = pkg.repo();
+        if version == "latest" {
+            return github::get_latest_release(&repo);
+        }
+        match github::get_release(&repo, &format!("{prefix}v{version}")) {
+            Err(e) if matches!(http::error_code(&e), Some(404)) => {
+                github::get_release(&repo, &format!("{prefix}{version}"))
+            }
+            r => r,
+        }
+    }
+
+    fn fetch_checksum(
+        &self,
+        pkg: &AquaPackage,
+        release: &GithubRelease,
+        tmpl: &HashMap<&str, String>,
+    ) -> Result<Option<String>> {
+        let checksum = match &pkg.checksum {
+            Some(checksum) if checksum.enabled.unwrap_or(true) => checksum,
+            _ => return Ok(None),
+        };
+        let algorithm = checksum.algorithm.as_deref().unwrap_or("sha256");
+        if algorithm != "sha256" {
+            debug!(
+                "skipping unsupported {algorithm} checksum for {}",
+                self.name()
+            );
+            return Ok(None);
+        }
+        let url = match checksum.r#type.as_deref() {
+            Some("github_release") => {
+                let name = render(checksum.asset.as_deref().unwrap_or_default(), tmpl)?;
+                match release.assets.iter().find(|a| a.name == name) {
+                    Some(asset) => asset.browser_download_url.clone(),
+                    None => bail!("checksum asset {name} not found in {}", pkg.repo()),
+                }
+            }
+            Some("http") => render(checksum.url.as_deref().unwrap_or_default(), tmpl)?,
+            _ => return Ok(None),
+        };
+        let text = HTTP.get_text(url)?;
+        Ok(hash::parse_checksum(&text, &tmpl["Asset"]))
+    }
+
+    fn install(
+        &self,
+        tv: &ToolVersion,
+        pkg: &AquaPackage,
+        archive: &Path,
+        tmpl: &HashMap<&str, String>,
+    ) -> Result<()> {
+        let install_path = tv.install_path();
+        let bin_dir = install_path.join("bin");
+        file::remove_all(&install_path)?;
+        file::create_dir_all(&bin_dir)?;
+        let files = pkg.files();
+        let format = pkg.format();
+        match format.as_str() {
+            "raw" => {
+                let exe = exe_name(&files[0].name);
+                file::copy(archive, bin_dir.join(&exe))?;
+                return file::make_executable(bin_dir.join(&exe));
+            }
+            "tar.gz" | "tgz" => file::untar(archive, &install_path)?,
+            "zip" => file::unzip(archive, &install_path)?,
+            "tar" | "tar.xz" | "txz" | "tar.bz2" | "tbz2" | "tar.zst" => {
+                file::untar_xy(archive, &install_path)?
+            }
+            "gz" => {
+                let exe = exe_name(&files[0].name);
+                let mut input = GzDecoder::new(File::open(archive)?);
+                let mut output = file::create(&bin_dir.join(&exe))?;
+                std::io::copy(&mut input, &mut output)?;
+                return file::make_executable(bin_dir.join(&exe));
+            }
+            format => bail!("aqua format {format} is not supported"),
+        }
+        for f in files {
+            let src = match &f.src {
+                Some(src) => render(src, tmpl)?,
+                None => f.name.clone(),
+            };
+            let src = install_path.join(exe_name(&src));
+            if !src.exists() {
+                bail!("{} not found in {}", display_path(&src), tmpl["Asset"]);
+            }
+            file::make_executable(&src)?;
+            let target = Path::new("..").join(src.strip_prefix(&install_path).unwrap_or(&src));
+            file::make_symlink_or_file(&target, &bin_dir.join(exe_name(&f.name)))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AquaRegistry {
+    #[serde(default)]
+    packages: Vec<AquaPackage>,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+struct AquaPackage {
+    name: Option<String>,
+    r#type: Option<String>,
+    repo_owner: Option<String>,
+    repo_name: Option<String>,
+    asset: Option<String>,
+    url: Option<String>,
+    format: Option<String>,
+    files: Option<Vec<AquaFile>>,
+    replacements: Option<HashMap<String, String>>,
+    format_overrides: Option<Vec<AquaFormatOverride>>,
+    checksum: Option<AquaChecksum>,
+    version_prefix: Option<String>,
+    rosetta2: Option<bool>,
+    version_constraint: Option<String>,
+    #[serde(default)]
+    version_overrides: Vec<AquaPackage>,
+    #[serde(default)]
+    overrides: Vec<AquaPackage>,
+    goos: Option<String>,
+    goarch: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AquaFile {
+    name: String,
+    src: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AquaFormatOverride {
+    goos: String,
+    format: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AquaChecksum {
+    r#type: Option<String>,
+    asset: Option<String>,
+    url: Option<String>,
+    algorithm: Option<String>,
+    enabled: Option<bool>,
+}
+
+impl AquaPackage {
+    fn repo(&self) -> String {
+        format!(
+            "{}/{}",
+            self.repo_owner.as_deref().unwrap_or_default(),
+            self.repo_name.as_deref().unwrap_or_default()
+        )
+    }
+
+    /// applies the first entry of `version_overrides` which matches if the package's own
+    /// `version_constraint` does not
+    fn for_version(&self, version: &str) -> AquaPackage {
+        let mut pkg = self.clone();
+        let satisfied =
+            |c: &Option<String>| c.as_deref().map_or(true, |c| version_matches(c, version));
+        if !satisfied(&self.version_constraint) {
+            let o = self
+                .version_overrides
+                .iter()
+                .find(|o| satisfied(&o.version_constraint));
+            if let Some(o) = o {
+                pkg.merge(o.clone());
+            }
+        }
+        pkg
+    }
+
+    /// applies `overrides` for this os/arch, e.g.: a different format on windows
+    fn for_platform(&self, goos: &str, goarch: &str) -> AquaPackage {
+        let mut pkg = self.clone();
+        if goos == "darwin" && goarch == "arm64" && pkg.rosetta2 == Some(true) {
+            return pkg.for_platform(goos, "amd64");
+        }
+        let o = self.overrides.iter().find(|o| {
+            o.goos.as_deref().map_or(true, |g| g == goos)
+                && o.goarch.as_deref().map_or(true, |a| a == goarch)
+        });
+        if let Some(o) = o {
+            pkg.merge(o.clone());
+        }
+        pkg.goos = Some(goos.to_string());
+        pkg.goarch = Some(goarch.to_string());
+        pkg
+    }
+
+    fn merge(&mut self, other: AquaPackage) {
+        macro_rules! merge {
+            ($($field:ident),*) => {
+                $(if other.$field.is_some() {
+                    self.$field = other.$field;
+                })*
+            };
+        }
+        merge!(
+            r#type,
+            repo_owner,
+            repo_name,
+            asset,
+            url,
+            format,
+            files,
+            replacements,
+            format_overrides,
+            checksum,
+            rosetta2
+        );
+    }
+
+    fn format(&self) -> String {
+        let goos = self.goos.as_deref().unwrap_or_else(|| goos());
+        self.format_overrides
+            .iter()
+            .flatten()
+            .find(|o| o.goos == goos)
+            .map(|o| o.format.clone())
+            .or_else(|| self.format.clone())
+            .unwrap_or_else(|| "raw".into())
+    }
+
+    fn files(&self) -> Vec<AquaFile> {
+        match &self.files {
+            Some(files) if !files.is_empty() => files.clone(),
+            _ => vec![AquaFile {
+                name: self.repo_name.clone().unwrap_or_default(),
+                src: None,
+            }],
+        }
+    }
+
+    fn template_vars(&self, tag: &str) -> HashMap<&'static str, String> {
+        let goos = self.goos.as_deref().unwrap_or_else(|| goos());
+        let goarch = self.goarch.as_deref().unwrap_or_else(|| goarch());
+        let replace = |s: &str| {
+            self.replacements
+                .as_ref()
+                .and_then(|r| r.get(s).cloned())
+                .unwrap_or_else(|| s.to_string())
+        };
+        let prefix = self.version_prefix.as_deref().unwrap_or_default();
+        HashMap::from([
+            ("Version", tag.to_string()),
+            (
+                "SemVer",
+                tag.strip_prefix(prefix).unwrap_or(tag).to_string(),
+            ),
+            ("OS", replace(goos)),
+            ("Arch", replace(goarch)),
+            ("GOOS", goos.to_string()),
+            ("GOARCH", goarch.to_string()),
+            ("Format", self.format()),
+        ])
+    }
+}
+
+fn goos() -> &'static str {
+    match OS.as_str() {
+        "macos" => "darwin",
+        os => os,
+    }
+}
+
+fn goarch() -> &'static str {
+    match ARCH.as_str() {
+        "x64" => "amd64",
+        arch => arch,
+    }
+}
+
+fn exe_name(name: &str) -> String {
+    match cfg!(windows) && Path::new(name).extension().is_none() {
+        true => format!("{name}.exe"),
+        false => name.to_string(),
+    }
+}
+
+fn asset_without_ext<'a>(asset: &'a str, format: &str) -> &'a str {
+    asset
+        .strip_suffix(&format!(".{format}"))
+        .or_else(|| asset.strip_suffix(".exe"))
+        .unwrap_or(asset)
+}
+
+/// renders the subset of go templates used in the aqua registry
+/// e.g.: "ripgrep-{{.Version}}-{{.Arch}}-{{.OS}}.{{.Format}}" or "{{trimV .Version}}"
+fn render(tmpl: &str, vars: &HashMap<&str, String>) -> Result<String> {
+    let mut err = None;
+    let out = regex!(r"\{\{-?\s*(.*?)\s*-?\}\}").replace_all(tmpl, |caps: &regex::Captures| {
+        let expr = caps[1].split_whitespace().collect::<Vec<_>>();
+        let mut var = |v: &str| {
+            v.strip_prefix('.')
+                .and_then(|v| vars.get(v).cloned())
+                .unwrap_or_else(|| {
+                    err = Some(eyre!("unknown variable {v} in {tmpl}"));
+                    String::new()
+                })
+        };
+        match expr.as_slice() {
+            [v] => var(v),
+            ["trimV", v] => var(v).trim_start_matches('v').to_string(),
+            ["title", v] => {
+                let v = var(v);
+                let mut chars = v.chars();
+                match chars.next() {
+                    Some(c) => c.to_uppercase().chain(chars).collect(),
+                    None => v,
+                }
+            }
+            _ => {
+                err = Some(eyre!(
+                    "unsupported template expression {} in {tmpl}",
+                    &caps[0]
+                ));
+                String::new()
+            }
+        }
+    });
+    match err {
+        Some(err) => Err(err),
+        None => Ok(out.to_string()),
+    }
+}
+
+/// evaluates the subset of aqua's version constraint expressions we support
+/// e.g.: `semver(">= 1.2.0")`, `Version == "v1.0.0"`, `"true"`
+fn version_matches(constraint: &str, version: &str) -> bool {
+    let constraint = constraint.trim();
+    if let Some(caps) = regex!(r#"^semver\("(.*)"\)$"#).captures(constraint) {
+        let v = match Versioning::new(version.trim_start_matches('v')) {
+            Some(v) => v,
+            None => return false,
+        };
+        return caps[1].split(',').all(|c| {
+            let c = c.trim();
+            let (op, expected) = match c.find(|ch: char| ch.is_ascii_digit() || ch == 'v') {
+                Some(i) => c.split_at(i),
+                None => return false,
+            };
+            let expected = match Versioning::new(expected.trim_start_matches('v')) {
+                Some(e) => e,
+                None => return false,
+            };
+            match op.trim() {
+                ">=" => v >= expected,
+                ">" => v > expected,
+                "<=" => v <= expected,
+                "<" => v < expected,
+                "!=" => v != expected,
+                "" | "=" | "==" => v == expected,
+                _ => false,
+            }
+        });
+    }
+    if let Some(caps) = regex!(r#"^Version\s*(==|!=)\s*"(.*)"$"#).captures(constraint) {
+        return (&caps[1] == "==") == (&caps[2] == version);
+    }
+    matches!(constraint, "true" | "\"true\"")
+}
+
+/// returns the aqua registry, cloning it into the cache if `aqua_registry_dir` is not set.
+/// Once cloned it is only updated weekly and failures to update are ignored so it works offline.
+fn registry_dir() -> Result<PathBuf> {
+    let settings = Settings::get();
+    if let Some(dir) = &settings.aqua_registry_dir {
+        return Ok(file::replace_path(dir));
+    }
+    let dir = dirs::CACHE.join("aqua-registry");
+    let git = Git::new(&dir);
+    if !git.exists() {
+        git.clone(&settings.aqua_registry_url)?;
+    } else if file::modified_duration(&dir)? > Duration::from_secs(60 * 60 * 24 * 7) {
+        if let Err(err) = git.update(None) {
+            debug!("failed to update aqua registry: {err:#}");
+        }
+        file::touch_dir(&dir)?;
+    }
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let vars = HashMap::from([
+            ("Version", "v1.2.3".to_string()),
+            ("OS", "darwin".to_string()),
+        ]);
+        assert_eq!(
+            render("foo-{{trimV .Version}}-{{ .OS }}", &vars).unwrap(),
+            "foo-1.2.3-darwin"
+        );
+        assert_eq!(render("{{title .OS}}", &vars).unwrap(), "Darwin");
+        assert!(render("{{.Missing}}", &vars).is_err());
+    }
+
+    #[test]
+    fn test_version_matches() {
+        assert!(version_matches(r#"semver(">= 13.0.0")"#, "14.1.0"));
+        assert!(!version_matches(r#"semver(">= 13.0.0")"#, "v12.1.0"));
+        assert!(version_matches(r#"semver(">= 1.0.0, < 2.0.0")"#, "1.5.0"));
+        assert!(version_matches(r#"Version == "v1.0.0""#, "v1.0.0"));
+        assert!(version_matches("true", "1.0.0"));
+        assert!(!version_matches("false", "1.0.0"));
+    }
+
+    #[test]
+    fn test_package_overrides() {
+        let registry: AquaRegistry = serde_yaml::from_str(
+            r#"
+packages:
+  - type: github_release
+    repo_owner: BurntSushi
+    repo_name: ripgrep
+    asset: ripgrep-{{.Version}}-{{.Arch}}-{{.OS}}.{{.Format}}
+    format: tar.gz
+    files:
+      - name: rg
+        src: "{{.AssetWithoutExt}}/rg"
+    replacements:
+      amd64: x86_64
+      darwin: apple-darwin
+      linux: unknown-linux-musl
+    format_overrides:
+      - goos: windows
+        format: zip
+    version_constraint: semver(">= 13.0.0")
+    version_overrides:
+      - version_constraint: "true"
+        asset: ripgrep-{{.Version}}-old.{{.Format}}
+"#,
+        )
+        .unwrap();
+        let pkg = &registry.packages[0];
+        let linux = pkg.for_version("14.1.0").for_platform("linux", "amd64");
+        let tmpl = linux.template_vars("14.1.0");
+        assert_eq!(
+            render(linux.asset.as_deref().unwrap(), &tmpl).unwrap(),
+            "ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz"
+        );
+        let windows = pkg.for_version("14.1.0").for_platform("windows", "amd64");
+        assert_eq!(windows.format(), "zip");
+        let old = pkg.for_version("12.0.0").for_platform("linux", "amd64");
+        let tmpl = old.template_vars("12.0.0");
+        assert_eq!(
+            render(old.asset.as_deref().unwrap(), &tmpl).unwrap(),
+            "ripgrep-12.0.0-old.tar.gz"
+        );
+        assert_eq!(
+            asset_without_ext("ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz", "tar.gz"),
+            "ripgrep-14.1.0-x86_64-unknown-linux-musl"
+        );
+    }
+}
@@ -15,7 +15,7 @@ use crate::backend::external_plugin_cache::ExternalPluginCache;
 use crate::backend::{ABackend, Backend, BackendList};
 use crate::cache::CacheManager;
 use crate::cli::args::BackendArg;
-use crate::config::Config;
+use crate::config::{Config, Settings};
 use crate::default_shorthands::DEFAULT_SHORTHANDS;
 use crate::env_diff::{EnvDiff, EnvDiffOperation};
 use crate::git::Git;
@@ -129,6 +129,7 @@ impl AsdfBackend {
     }
 
     fn fetch_remote_versions(&self) -> Result<Vec<String>> {
+        Settings::get().ensure_online(&format!("listing remote versions of {}", self.name))?;
         match self.fetch_versions() {
             Ok(Some(versions)) => return Ok(versions),
             Err(err) => warn!(
@@ -230,7 +231,9 @@ impl AsdfBackend {
             sm = sm.with_env("MISE_PROJECT_ROOT", project_root);
         }
         let install_type = match &tv.request {
-            ToolRequest::Version { .. } | ToolRequest::Prefix { .. } => "version",
+            ToolRequest::Version { .. }
+            | ToolRequest::Prefix { .. }
+            | ToolRequest::Range { .. } => "version",
             ToolRequest::Ref { .. } => "ref",
             ToolRequest::Path(_, _) => "path",
             ToolRequest::Sub { .. } => "sub",
@@ -390,6 +393,14 @@ impl Backend for AsdfBackend {
         Some(self.plugin())
     }
 
+    fn plugin_path(&self) -> Option<PathBuf> {
+        Some(self.plugin.plugin_path.clone())
+    }
+
+    /// plugin install scripts are free to bake ASDF_INSTALL_PATH into what they build
+    fn is_relocatable(&self) -> bool {
+        false
+    }
     fn install_version_impl(&self, ctx: &InstallContext) -> Result<()> {
         let mut sm = self.script_man_for_tv(&ctx.tv)?;
 
@@ -5,6 +5,7 @@ use eyre::eyre;
 use serde_json::Deserializer;
 use url::Url;
 
+use crate::backend::version_info::VersionInfo;
 use crate::backend::{Backend, BackendType};
 use crate::cache::CacheManager;
 use crate::cli::args::BackendArg;
@@ -14,12 +15,12 @@ use crate::env::GITHUB_TOKEN;
 use crate::file;
 use crate::http::HTTP_FETCH;
 use crate::install_context::InstallContext;
-use crate::toolset::ToolRequest;
+use crate::toolset::{ToolRequest, ToolVersion};
 
 #[derive(Debug)]
 pub struct CargoBackend {
     ba: BackendArg,
-    remote_version_cache: CacheManager<Vec<String>>,
+    remote_version_cache: CacheManager<Vec<VersionInfo>>,
 }
 
 impl Backend for CargoBackend {
@@ -36,9 +37,19 @@ impl Backend for CargoBackend {
     }
 
     fn _list_remote_versions(&self) -> eyre::Result<Vec<String>> {
+        // yanked crates are only installed if requested exactly so they aren't listed
+        Ok(self
+            ._list_remote_versions_info()?
+            .into_iter()
+            .filter(|v| !v.yanked)
+            .map(|v| v.version)
+            .collect())
+    }
+
+    fn _list_remote_versions_info(&self) -> eyre::Result<Vec<VersionInfo>> {
         if self.git_url().is_some() {
             // TODO: maybe fetch tags/branches from git?
-            return Ok(vec!["HEAD".into()]);
+            return Ok(vec![VersionInfo::new("HEAD")]);
         }
         self.remote_version_cache
             .get_or_try_init(|| {
@@ -47,9 +58,13 @@ impl Backend for CargoBackend {
                 let mut versions = vec![];
                 for v in stream {
                     let v = v?;
-                    if !v.yanked {
-                        versions.push(v.vers);
-                    }
+                    versions.push(VersionInfo {
+                        // semver prereleases, e.g.: "0.19.0-beta.1"
+                        prerelease: v.vers.contains('-'),
+                        yanked: v.yanked,
+                        version: v.vers,
+                        ..Default::default()
+                    });
                 }
                 Ok(versions)
             })
@@ -99,13 +114,26 @@ impl Backend for CargoBackend {
 
         Ok(())
     }
+
+    fn license(&self, tv: &ToolVersion) -> eyre::Result<Option<String>> {
+        if self.git_url().is_some() {
+            return Ok(None);
+        }
+        let url = format!(
+            "https://crates.io/api/v1/crates/{}/{}",
+            self.name(),
+            tv.version
+        );
+        let res: CratesIoVersionResponse = HTTP_FETCH.json(url)?;
+        Ok(res.version.license)
+    }
 }
 
 impl CargoBackend {
     pub fn from_arg(ba: BackendArg) -> Self {
         Self {
             remote_version_cache: CacheManager::new(
-                ba.cache_path.join("remote_versions-$KEY.msgpack.z"),
+                ba.cache_path.join("remote_versions_info-$KEY.msgpack.z"),
             ),
             ba,
         }
@@ -145,3 +173,14 @@ struct CrateVersion {
     vers: String,
     yanked: bool,
 }
+
+/// https://crates.io/api/v1/crates/<name>/<version>
+#[derive(Debug, serde::Deserialize)]
+struct CratesIoVersionResponse {
+    version: CratesIoVersion,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct CratesIoVersion {
+    license: Option<String>,
+}
@@ -0,0 +1,695 @@
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt::{Debug, Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use eyre::{bail, eyre, Result};
+use serde_derive::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::backend::{Backend, BackendType};
+use crate::cache::CacheManager;
+use crate::cli::args::BackendArg;
+use crate::cli::version::{ARCH, OS};
+use crate::config::Settings;
+use crate::http::HTTP_FETCH;
+use crate::install_context::InstallContext;
+use crate::toolset::ToolVersion;
+use crate::{dirs, download_cache, env, file, hash, lockfile};
+
+/// the placeholder conda-build writes into files when `has_prefix` doesn't specify one
+const DEFAULT_PREFIX_PLACEHOLDER: &str = "/opt/anaconda1anaconda2anaconda3";
+
+/// installs packages from a conda channel (e.g.: conda-forge) without conda or python by
+/// resolving the package and its dependencies from the channel's repodata.json and unpacking
+/// them all into the install path
+#[derive(Debug)]
+pub struct CondaBackend {
+    ba: BackendArg,
+    remote_version_cache: CacheManager<Vec<String>>,
+}
+
+impl Backend for CondaBackend {
+    fn get_type(&self) -> BackendType {
+        BackendType::Conda
+    }
+
+    fn fa(&self) -> &BackendArg {
+        &self.ba
+    }
+
+    fn _list_remote_versions(&self) -> Result<Vec<String>> {
+        self.remote_version_cache
+            .get_or_try_init(|| {
+                let mut versions = channel_packages()?
+                    .into_iter()
+                    .filter(|p| p.name == self.name())
+                    .map(|p| p.version)
+                    .collect::<Vec<_>>();
+                versions.sort_by(|a, b| compare_versions(a, b));
+                versions.dedup();
+                Ok(versions)
+            })
+            .cloned()
+    }
+
+    /// packages have their prefix placeholder replaced with the install path
+    fn is_relocatable(&self) -> bool {
+        false
+    }
+    fn install_version_impl(&self, ctx: &InstallContext) -> Result<()> {
+        let settings = Settings::get();
+        settings.ensure_experimental("conda backend")?;
+        let packages = channel_packages()?;
+        let solved = solve(&packages, self.name(), &ctx.tv.version)?;
+
+        let install_path = ctx.tv.install_path();
+        file::remove_all(&install_path)?;
+        file::create_dir_all(&install_path)?;
+        for pkg in solved {
+            ctx.pr.set_message(format!("downloading {}", pkg.filename));
+            let archive = self.download(ctx, pkg)?;
+            ctx.pr.set_message(format!("installing {}", pkg.filename));
+            install_package(&ctx.tv, pkg, &archive)?;
+        }
+        Ok(())
+    }
+}
+
+impl CondaBackend {
+    pub fn from_arg(ba: BackendArg) -> Self {
+        Self {
+            remote_version_cache: CacheManager::new(
+                ba.cache_path.join("remote_versions-$KEY.msgpack.z"),
+            )
+            .with_fresh_duration(*env::MISE_FETCH_REMOTE_VERSIONS_CACHE),
+            ba,
+        }
+    }
+
+    fn download(&self, ctx: &InstallContext, pkg: &Package) -> Result<PathBuf> {
+        let tv = &ctx.tv;
+        let url = format!("{}/{}/{}", channel(), pkg.subdir, pkg.filename);
+        let archive = tv.download_path().join(&pkg.filename);
+        file::create_dir_all(tv.download_path())?;
+        let verify = |archive: &Path| match &pkg.sha256 {
+            Some(sha256) => hash::ensure_checksum_sha256(archive, sha256, Some(ctx.pr.as_ref())),
+            None => Ok(()),
+        };
+        if is_remote(&url) {
+            download_cache::download(&url, &archive, Some(ctx.pr.as_ref()), verify)?;
+        } else {
+            file::copy(file::replace_path(&url), &archive)?;
+            verify(&archive)?;
+        }
+        // the lockfile has one checksum per tool so only the requested package is recorded
+        if pkg.name == self.name() {
+            lockfile::record_download(tv, &url, &archive)?;
+        }
+        Ok(archive)
+    }
+}
+
+fn channel() -> String {
+    Settings::get()
+        .conda_channel
+        .trim_end_matches('/')
+        .to_string()
+}
+
+fn is_remote(url: &str) -> bool {
+    url.starts_with("http://") || url.starts_with("https://")
+}
+
+/// the conda subdir for the current platform, e.g.: "linux-64" or "osx-arm64"
+fn platform_subdir() -> String {
+    let os = match OS.as_str() {
+        "macos" => "osx",
+        "windows" => "win",
+        os => os,
+    };
+    let arch = match ARCH.as_str() {
+        "x64" => "64",
+        "arm64" if os == "linux" => "aarch64",
+        arch => arch,
+    };
+    format!("{os}-{arch}")
+}
+
+/// all packages in the configured channel for this platform and noarch
+fn channel_packages() -> Result<Vec<Package>> {
+    let channel = channel();
+    let mut packages = vec![];
+    for subdir in [platform_subdir(), "noarch".into()] {
+        let cache: CacheManager<Vec<Package>> = CacheManager::new(dirs::CACHE.join("conda").join(
+            format!("{}-{subdir}-$KEY.msgpack.z", hash::hash_to_str(&channel)),
+        ))
+        .with_fresh_duration(*env::MISE_FETCH_REMOTE_VERSIONS_CACHE);
+        let subdir_packages = cache.get_or_try_init(|| fetch_repodata(&channel, &subdir))?;
+        packages.extend(subdir_packages.iter().cloned());
+    }
+    Ok(packages)
+}
+
+fn fetch_repodata(channel: &str, subdir: &str) -> Result<Vec<Package>> {
+    let url = format!("{channel}/{subdir}/repodata.json");
+    let repodata: Repodata = if is_remote(&url) {
+        match HTTP_FETCH.json(&url) {
+            Ok(repodata) => repodata,
+            // not every channel has every subdir
+            Err(err) if crate::http::error_code(&err) == Some(404) => Repodata::default(),
+            Err(err) => return Err(err),
+        }
+    } else {
+        let path = file::replace_path(&url);
+        if !path.exists() {
+            return Ok(vec![]);
+        }
+        serde_json::from_str(&file::read_to_string(&path)?)?
+    };
+    Ok(repodata.into_packages(subdir))
+}
+
+/// picks the newest version of each package needed to install `name@version`. This is a greedy
+/// resolver: the first package chosen for a name is kept and later constraints on it must agree
+/// with that choice rather than triggering a backtrack.
+fn solve<'a>(packages: &'a [Package], name: &str, version: &str) -> Result<Vec<&'a Package>> {
+    let mut solved: Vec<&Package> = vec![];
+    let mut queue = VecDeque::from([MatchSpec {
+        name: name.to_string(),
+        version: Some(format!("=={version}")),
+        build: None,
+    }]);
+    while let Some(spec) = queue.pop_front() {
+        // virtual packages like __glibc are provided by the system
+        if spec.name.starts_with("__") {
+            continue;
+        }
+        if let Some(pkg) = solved.iter().find(|p| p.name == spec.name) {
+            if !spec.matches(pkg) {
+                bail!("conflict: {spec} is required but {pkg} was already selected");
+            }
+            continue;
+        }
+        let pkg = packages
+            .iter()
+            .filter(|p| spec.matches(p))
+            .max_by(|a, b| {
+                compare_versions(&a.version, &b.version)
+                    .then(a.build_number.cmp(&b.build_number))
+                    .then(
+                        a.filename
+                            .ends_with(".conda")
+                            .cmp(&b.filename.ends_with(".conda")),
+                    )
+            })
+            .ok_or_else(|| eyre!("no package found matching {spec}"))?;
+        trace!("conda: {spec} -> {pkg}");
+        for dep in &pkg.depends {
+            queue.push_back(MatchSpec::parse(dep)?);
+        }
+        solved.push(pkg);
+    }
+    Ok(solved)
+}
+
+/// unpacks a package into the install path and replaces its prefix placeholders
+fn install_package(tv: &ToolVersion, pkg: &Package, archive: &Path) -> Result<()> {
+    let staging = tv.download_path().join(pkg.to_string());
+    file::remove_all(&staging)?;
+    file::create_dir_all(&staging)?;
+    if pkg.filename.ends_with(".conda") {
+        // .conda files are zip archives of an info-*.tar.zst and a pkg-*.tar.zst
+        let unzipped = tv.download_path().join(format!("{pkg}.unzipped"));
+        file::remove_all(&unzipped)?;
+        file::create_dir_all(&unzipped)?;
+        file::unzip(archive, &unzipped)?;
+        for tarball in file::ls(&unzipped)? {
+            if tarball.to_string_lossy().ends_with(".tar.zst") {
+                file::untar_xy(&tarball, &staging)?;
+            }
+        }
+        file::remove_all(&unzipped)?;
+    } else {
+        file::untar_xy(archive, &staging)?;
+    }
+    let install_path = tv.install_path();
+    let has_prefix = staging.join("info").join("has_prefix");
+    if has_prefix.exists() {
+        for entry in parse_has_prefix(&file::read_to_string(&has_prefix)?) {
+            replace_prefix(&staging.join(&entry.path), &entry, &install_path)?;
+        }
+    }
+    file::remove_all(staging.join("info"))?;
+    merge_dir(&staging, &install_path)?;
+    file::remove_all(&staging)
+}
+
+/// moves everything in `from` into `to`, keeping symlinks as they are
+fn merge_dir(from: &Path, to: &Path) -> Result<()> {
+    let entries = WalkDir::new(from)
+        .min_depth(1)
+        .into_iter()
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    for entry in entries {
+        let dest = to.join(entry.path().strip_prefix(from)?);
+        if entry.file_type().is_dir() {
+            file::create_dir_all(&dest)?;
+        } else {
+            if dest.symlink_metadata().is_ok() {
+                file::remove_file(&dest)?;
+            }
+            file::rename(entry.path(), &dest)?;
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, PartialEq)]
+struct HasPrefix {
+    placeholder: String,
+    binary: bool,
+    path: String,
+}
+
+/// parses info/has_prefix which lists files that contain the build prefix, either as
+/// `<placeholder> <text|binary> <path>` or just `<path>`
+fn parse_has_prefix(text: &str) -> Vec<HasPrefix> {
+    text.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|line| {
+            let parts = line.splitn(3, ' ').collect::<Vec<_>>();
+            match parts.as_slice() {
+                [placeholder, mode, path] => HasPrefix {
+                    placeholder: placeholder.to_string(),
+                    binary: *mode == "binary",
+                    path: path.trim_matches('"').to_string(),
+                },
+                _ => HasPrefix {
+                    placeholder: DEFAULT_PREFIX_PLACEHOLDER.to_string(),
+                    binary: false,
+                    path: line.trim().to_string(),
+                },
+            }
+        })
+        .collect()
+}
+
+fn replace_prefix(path: &Path, entry: &HasPrefix, prefix: &Path) -> Result<()> {
+    if !path.is_file() {
+        return Ok(());
+    }
+    let placeholder = entry.placeholder.as_bytes();
+    let prefix = prefix.to_string_lossy();
+    let prefix = prefix.as_bytes();
+    let data = std::fs::read(path)?;
+    let data = if entry.binary {
+        replace_prefix_binary(&data, placeholder, prefix)
+            .ok_or_else(|| eyre!("install path is too long to relocate {}", path.display()))?
+    } else {
+        replace_bytes(&data, placeholder, prefix)
+    };
+    file::write(path, data)
+}
+
+fn replace_bytes(data: &[u8], from: &[u8], to: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        if data[i..].starts_with(from) {
+            out.extend_from_slice(to);
+            i += from.len();
+        } else {
+            out.push(data[i]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// replaces the placeholder in each null-terminated string it appears in and pads the string
+/// with nulls so offsets in the binary don't change
+fn replace_prefix_binary(data: &[u8], placeholder: &[u8], prefix: &[u8]) -> Option<Vec<u8>> {
+    if prefix.len() > placeholder.len() {
+        return None;
+    }
+    let mut out = Vec::with_capacity(data.len());
+    let mut i = 0;
+    while i < data.len() {
+        if !data[i..].starts_with(placeholder) {
+            out.push(data[i]);
+            i += 1;
+            continue;
+        }
+        let end = data[i..]
+            .iter()
+            .position(|b| *b == 0)
+            .map_or(data.len(), |p| i + p);
+        let replaced = replace_bytes(&data[i..end], placeholder, prefix);
+        let padding = end - i - replaced.len();
+        out.extend_from_slice(&replaced);
+        out.extend(std::iter::repeat(0).take(padding));
+        i = end;
+    }
+    Some(out)
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct Repodata {
+    #[serde(default)]
+    packages: BTreeMap<String, PackageRecord>,
+    #[serde(default, rename = "packages.conda")]
+    packages_conda: BTreeMap<String, PackageRecord>,
+}
+
+impl Repodata {
+    fn into_packages(self, subdir: &str) -> Vec<Package> {
+        self.packages
+            .into_iter()
+            .chain(self.packages_conda)
+            .map(|(filename, r)| Package {
+                filename,
+                subdir: subdir.to_string(),
+                name: r.name,
+                version: r.version,
+                build: r.build,
+                build_number: r.build_number,
+                depends: r.depends,
+                sha256: r.sha256,
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PackageRecord {
+    name: String,
+    version: String,
+    #[serde(default)]
+    build: String,
+    #[serde(default)]
+    build_number: u64,
+    #[serde(default)]
+    depends: Vec<String>,
+    sha256: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Package {
+    filename: String,
+    subdir: String,
+    name: String,
+    version: String,
+    build: String,
+    build_number: u64,
+    depends: Vec<String>,
+    sha256: Option<String>,
+}
+
+impl Display for Package {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}-{}", self.name, self.version, self.build)
+    }
+}
+
+/// a dependency as written in repodata.json, e.g.: "openssl >=3.1.0,<4.0a0" or
+/// "python 3.11.* *_cpython"
+#[derive(Debug)]
+struct MatchSpec {
+    name: String,
+    version: Option<String>,
+    build: Option<String>,
+}
+
+impl MatchSpec {
+    fn parse(spec: &str) -> Result<Self> {
+        let mut parts = spec.split_whitespace();
+        let name = parts
+            .next()
+            .ok_or_else(|| eyre!("invalid conda dependency: {spec:?}"))?;
+        Ok(Self {
+            name: name.to_string(),
+            version: parts.next().map(|s| s.to_string()),
+            build: parts.next().map(|s| s.to_string()),
+        })
+    }
+
+    fn matches(&self, pkg: &Package) -> bool {
+        pkg.name == self.name
+            && self
+                .version
+                .as_ref()
+                .map_or(true, |v| version_matches(v, &pkg.version))
+            && self
+                .build
+                .as_ref()
+                .map_or(true, |b| glob_matches(b, &pkg.build))
+    }
+}
+
+impl Display for MatchSpec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(version) = &self.version {
+            write!(f, " {version}")?;
+        }
+        if let Some(build) = &self.build {
+            write!(f, " {build}")?;
+        }
+        Ok(())
+    }
+}
+
+/// checks a version against a conda version spec where `|` is "or" and `,` is "and",
+/// e.g.: ">=1.2,<2.0a0|3.*"
+fn version_matches(spec: &str, version: &str) -> bool {
+    spec.split('|').any(|any| {
+        any.split(',').all(|c| {
+            let c = c.trim();
+            let (op, v) = match c.find(|ch: char| !"<>=!~".contains(ch)) {
+                Some(i) => c.split_at(i),
+                None => return false,
+            };
+            if v == "*" {
+                return op != "!=";
+            }
+            if let Some(prefix) = v.strip_suffix('*') {
+                let prefix = prefix.trim_end_matches('.');
+                let matches = version == prefix || version.starts_with(&format!("{prefix}."));
+                return match op {
+                    "!=" => !matches,
+                    "" | "=" | "==" => matches,
+                    _ => compare_op(op, compare_versions(version, prefix)),
+                };
+            }
+            match op {
+                // "=1.2" is a fuzzy match on 1.2.*
+                "=" => version == v || version.starts_with(&format!("{v}.")),
+                "~=" => {
+                    let prefix = v.rsplit_once('.').map_or(v, |(p, _)| p);
+                    compare_versions(version, v) != Ordering::Less
+                        && (version == prefix || version.starts_with(&format!("{prefix}.")))
+                }
+                op => compare_op(op, compare_versions(version, v)),
+            }
+        })
+    })
+}
+
+fn compare_op(op: &str, ord: Ordering) -> bool {
+    match op {
+        "" | "==" => ord == Ordering::Equal,
+        "!=" => ord != Ordering::Equal,
+        ">=" => ord != Ordering::Less,
+        "<=" => ord != Ordering::Greater,
+        ">" => ord == Ordering::Greater,
+        "<" => ord == Ordering::Less,
+        _ => false,
+    }
+}
+
+fn glob_matches(pattern: &str, s: &str) -> bool {
+    let re = pattern
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(".*");
+    regex::Regex::new(&format!("^{re}$")).is_ok_and(|re| re.is_match(s))
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum VersionPart {
+    Str(String),
+    Num(u64),
+}
+
+/// compares versions the way conda does: segments are split on "." and "_", letters sort before
+/// numbers (so 2.0a0 < 2.0), "dev" sorts before other letters and "post" after everything
+fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a, b) = (parse_version(a), parse_version(b));
+    let zero = vec![VersionPart::Num(0)];
+    for i in 0..a.len().max(b.len()) {
+        let (sa, sb) = (a.get(i).unwrap_or(&zero), b.get(i).unwrap_or(&zero));
+        for j in 0..sa.len().max(sb.len()) {
+            let (pa, pb) = (
+                sa.get(j).unwrap_or(&VersionPart::Num(0)),
+                sb.get(j).unwrap_or(&VersionPart::Num(0)),
+            );
+            let ord = match (pa, pb) {
+                (VersionPart::Str(x), VersionPart::Str(y)) => {
+                    version_str_key(x).cmp(&version_str_key(y))
+                }
+                (VersionPart::Str(x), VersionPart::Num(_)) if x == "post" => Ordering::Greater,
+                (VersionPart::Num(_), VersionPart::Str(y)) if y == "post" => Ordering::Less,
+                (pa, pb) => pa.cmp(pb),
+            };
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+    }
+    Ordering::Equal
+}
+
+fn version_str_key(s: &str) -> (u8, &str) {
+    match s {
+        "dev" => (0, s),
+        "post" => (2, s),
+        s => (1, s),
+    }
+}
+
+fn parse_version(version: &str) -> Vec<Vec<VersionPart>> {
+    let version = version.to_lowercase();
+    let version = version.split_once('!').map_or(version.as_str(), |(_, v)| v);
+    let version = version.split_once('+').map_or(version, |(v, _)| v);
+    version
+        .split(['.', '_', '-'])
+        .map(|segment| {
+            let mut parts = regex!(r"\d+|[^\d]+")
+                .find_iter(segment)
+                .map(|m| match m.as_str().parse() {
+                    Ok(n) => VersionPart::Num(n),
+                    Err(_) => VersionPart::Str(m.as_str().to_string()),
+                })
+                .collect::<Vec<_>>();
+            if matches!(parts.first(), Some(VersionPart::Str(_))) {
+                parts.insert(0, VersionPart::Num(0));
+            }
+            parts
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn pkg(name: &str, version: &str, build_number: u64, depends: &[&str]) -> Package {
+        Package {
+            filename: format!("{name}-{version}-h_{build_number}.tar.bz2"),
+            subdir: "linux-64".into(),
+            name: name.into(),
+            version: version.into(),
+            build: format!("h_{build_number}"),
+            build_number,
+            depends: depends.iter().map(|d| d.to_string()).collect(),
+            sha256: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.2.10", "1.2.9"), Ordering::Greater);
+        assert_eq!(compare_versions("2.0a0", "2.0"), Ordering::Less);
+        assert_eq!(compare_versions("2.0", "2.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("1.1dev", "1.1a1"), Ordering::Less);
+        assert_eq!(compare_versions("1.1.post1", "1.1"), Ordering::Greater);
+        assert_eq!(compare_versions("3.1.1", "3.1.1_1"), Ordering::Less);
+    }
+
+    #[test]
+    fn test_version_matches() {
+        assert!(version_matches(">=3.1.0,<4.0a0", "3.3.1"));
+        assert!(!version_matches(">=3.1.0,<4.0a0", "4.0.0"));
+        assert!(version_matches("3.11.*", "3.11.9"));
+        assert!(!version_matches("3.11.*", "3.1.9"));
+        assert!(version_matches("1.2|>=2", "2.5"));
+        assert!(version_matches("1.2.13", "1.2.13"));
+        assert!(!version_matches("1.2.13", "1.2.14"));
+        assert!(version_matches("=1.2", "1.2.5"));
+        assert!(version_matches("~=1.2.3", "1.2.9"));
+        assert!(!version_matches("~=1.2.3", "1.3.0"));
+        assert!(version_matches("!=1.0", "1.1"));
+    }
+
+    #[test]
+    fn test_match_spec() {
+        let spec = MatchSpec::parse("python 3.11.* *_cpython").unwrap();
+        let mut python = pkg("python", "3.11.9", 0, &[]);
+        assert!(!spec.matches(&python));
+        python.build = "h123_0_cpython".into();
+        assert!(spec.matches(&python));
+        assert_eq!(spec.to_string(), "python 3.11.* *_cpython");
+    }
+
+    #[test]
+    fn test_solve() {
+        let packages = vec![
+            pkg(
+                "gdal",
+                "3.9.0",
+                0,
+                &["libzlib >=1.2.13,<2.0a0", "__glibc >=2.17"],
+            ),
+            pkg("gdal", "3.9.1", 0, &["libzlib >=1.3,<2.0a0"]),
+            pkg("libzlib", "1.2.13", 0, &[]),
+            pkg("libzlib", "1.3.1", 0, &[]),
+            pkg("libzlib", "1.3.1", 1, &[]),
+            pkg("libzlib", "2.0.0", 0, &[]),
+        ];
+        let solved = solve(&packages, "gdal", "3.9.0")
+            .unwrap()
+            .into_iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(solved, vec!["gdal-3.9.0-h_0", "libzlib-1.3.1-h_1"]);
+        assert!(solve(&packages, "gdal", "4.0.0").is_err());
+    }
+
+    #[test]
+    fn test_has_prefix() {
+        let entries = parse_has_prefix("/opt/placeholder binary lib/libfoo.so\nbin/foo-config\n");
+        assert_eq!(
+            entries,
+            vec![
+                HasPrefix {
+                    placeholder: "/opt/placeholder".into(),
+                    binary: true,
+                    path: "lib/libfoo.so".into(),
+                },
+                HasPrefix {
+                    placeholder: DEFAULT_PREFIX_PLACEHOLDER.into(),
+                    binary: false,
+                    path: "bin/foo-config".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_replace_prefix_binary() {
+        let data = b"xx/opt/placeholder/lib\0yy";
+        assert_eq!(
+            replace_prefix_binary(data, b"/opt/placeholder", b"/p").unwrap(),
+            b"xx/p/lib\0\0\0\0\0\0\0\0\0\0\0\0\0\0\0yy".to_vec()
+        );
+        assert!(replace_prefix_binary(data, b"/opt/placeholder", b"/a/very/long/prefix").is_none());
+        assert_eq!(
+            replace_bytes(b"#!/opt/placeholder/bin/sh", b"/opt/placeholder", b"/p"),
+            b"#!/p/bin/sh".to_vec()
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+
+use eyre::eyre;
+use serde_derive::Deserialize;
+use versions::Versioning;
+
+use crate::backend::{Backend, BackendType};
+use crate::cache::CacheManager;
+use crate::cli::args::BackendArg;
+use crate::cmd::CmdLineRunner;
+use crate::config::{Config, Settings};
+use crate::file;
+use crate::http::HTTP_FETCH;
+use crate::install_context::InstallContext;
+use crate::toolset::{ToolRequest, ToolVersion};
+
+/// installs dotnet global tools with `dotnet tool install --tool-path`
+#[derive(Debug)]
+pub struct DotnetBackend {
+    ba: BackendArg,
+    remote_version_cache: CacheManager<Vec<String>>,
+}
+
+impl Backend for DotnetBackend {
+    fn get_type(&self) -> BackendType {
+        BackendType::Dotnet
+    }
+
+    fn fa(&self) -> &BackendArg {
+        &self.ba
+    }
+
+    fn get_dependencies(&self, _tvr: &ToolRequest) -> eyre::Result<Vec<BackendArg>> {
+        Ok(vec!["dotnet".into()])
+    }
+
+    fn _list_remote_versions(&self) -> eyre::Result<Vec<String>> {
+        self.remote_version_cache
+            .get_or_try_init(|| {
+                let settings = Settings::get();
+                let versions = list_versions(&settings.dotnet_nuget_source, self.name())?;
+                Ok(versions.into_iter().filter(|v| !v.contains('-')).collect())
+            })
+            .cloned()
+    }
+
+    fn install_version_impl(&self, ctx: &InstallContext) -> eyre::Result<()> {
+        let config = Config::try_get()?;
+        let settings = Settings::get();
+        settings.ensure_experimental("dotnet backend")?;
+
+        CmdLineRunner::new("dotnet")
+            .arg("tool")
+            .arg("install")
+            .arg(self.name())
+            .arg("--version")
+            .arg(&ctx.tv.version)
+            .arg("--tool-path")
+            .arg(ctx.tv.install_path())
+            .arg("--add-source")
+            .arg(&settings.dotnet_nuget_source)
+            .with_pr(ctx.pr.as_ref())
+            .envs(ctx.ts.env_with_path(&config)?)
+            .prepend_path(ctx.ts.list_paths())?
+            .execute()?;
+
+        Ok(())
+    }
+
+    fn list_bin_paths(&self, tv: &ToolVersion) -> eyre::Result<Vec<PathBuf>> {
+        match tv.request {
+            ToolRequest::System(_) => Ok(vec![]),
+            _ => Ok(vec![tv.install_short_path()]),
+        }
+    }
+}
+
+impl DotnetBackend {
+    pub fn from_arg(ba: BackendArg) -> Self {
+        Self {
+            remote_version_cache: CacheManager::new(
+                ba.cache_path.join("remote_versions-$KEY.msgpack.z"),
+            ),
+            ba,
+        }
+    }
+}
+
+/// lists the versions of a package from either a NuGet v3 feed (e.g.:
+/// https://api.nuget.org/v3/index.json) or a local folder feed
+fn list_versions(source: &str, package: &str) -> eyre::Result<Vec<String>> {
+    let id = package.to_lowercase();
+    if !source.starts_with("http://") && !source.starts_with("https://") {
+        return list_local_versions(&file::replace_path(source), &id);
+    }
+    let index: NugetServiceIndex = HTTP_FETCH.json(source)?;
+    let base = index
+        .resources
+        .iter()
+        .find(|r| r.r#type.starts_with("PackageBaseAddress/3.0.0"))
+        .map(|r| r.id.trim_end_matches('/').to_string())
+        .ok_or_else(|| eyre!("no PackageBaseAddress resource found in {source}"))?;
+    let versions: NugetVersions = HTTP_FETCH.json(format!("{base}/{id}/index.json"))?;
+    Ok(versions.versions)
+}
+
+/// local feeds use the hierarchical layout created by `nuget add`: <feed>/<id>/<version>/
+fn list_local_versions(dir: &Path, id: &str) -> eyre::Result<Vec<String>> {
+    let dir = dir.join(id);
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    let mut versions = file::dir_subdirs(&dir)?;
+    versions.sort_by_cached_key(|v| Versioning::new(v));
+    Ok(versions)
+}
+
+#[derive(Debug, Deserialize)]
+struct NugetServiceIndex {
+    resources: Vec<NugetResource>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NugetResource {
+    #[serde(rename = "@id")]
+    id: String,
+    #[serde(rename = "@type")]
+    r#type: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct NugetVersions {
+    versions: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_list_local_versions() {
+        let feed = tempfile::tempdir().unwrap();
+        for v in ["8.0.1", "8.0.10", "8.0.2"] {
+            file::create_dir_all(feed.path().join("dotnet-ef").join(v)).unwrap();
+        }
+        let source = feed.path().to_string_lossy().to_string();
+        assert_eq!(
+            list_versions(&source, "dotnet-ef").unwrap(),
+            vec!["8.0.1", "8.0.2", "8.0.10"]
+        );
+        assert!(list_versions(&source, "csharpier").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_nuget_service_index() {
+        let index: NugetServiceIndex = serde_json::from_str(
+            r#"{"version": "3.0.0", "resources": [
+                {"@id": "https://api.nuget.org/v3-flatcontainer/", "@type": "PackageBaseAddress/3.0.0"}
+            ]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            index.resources[0].id,
+            "https://api.nuget.org/v3-flatcontainer/"
+        );
+    }
+}
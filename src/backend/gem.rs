@@ -0,0 +1,131 @@
+use std::fmt::Debug;
+use std::path::PathBuf;
+
+use serde_derive::Deserialize;
+
+use crate::backend::{Backend, BackendType};
+use crate::cache::CacheManager;
+use crate::cli::args::BackendArg;
+use crate::cmd::CmdLineRunner;
+use crate::config::{Config, Settings};
+use crate::file;
+use crate::http::HTTP_FETCH;
+use crate::install_context::InstallContext;
+use crate::toolset::{ToolRequest, ToolVersion};
+
+#[derive(Debug)]
+pub struct GemBackend {
+    ba: BackendArg,
+    remote_version_cache: CacheManager<Vec<String>>,
+}
+
+impl Backend for GemBackend {
+    fn get_type(&self) -> BackendType {
+        BackendType::Gem
+    }
+
+    fn fa(&self) -> &BackendArg {
+        &self.ba
+    }
+
+    fn get_dependencies(&self, _tvr: &ToolRequest) -> eyre::Result<Vec<BackendArg>> {
+        Ok(vec!["ruby".into()])
+    }
+
+    fn _list_remote_versions(&self) -> eyre::Result<Vec<String>> {
+        self.remote_version_cache
+            .get_or_try_init(|| {
+                let settings = Settings::get();
+                let url = format!(
+                    "{}/api/v1/versions/{}.json",
+                    settings.gem_source.trim_end_matches('/'),
+                    self.name()
+                );
+                let versions: Vec<GemVersion> = HTTP_FETCH.json(url)?;
+                Ok(versions
+                    .into_iter()
+                    .filter(|v| !v.prerelease)
+                    .map(|v| v.number)
+                    .rev()
+                    .collect())
+            })
+            .cloned()
+    }
+
+    /// the bin wrappers set GEM_HOME to an absolute path
+    fn is_relocatable(&self) -> bool {
+        false
+    }
+    fn install_version_impl(&self, ctx: &InstallContext) -> eyre::Result<()> {
+        let config = Config::try_get()?;
+        let settings = Settings::get();
+        settings.ensure_experimental("gem backend")?;
+        let gem_home = gem_home(&ctx.tv);
+
+        CmdLineRunner::new("gem")
+            .arg("install")
+            .arg(self.name())
+            .arg("--version")
+            .arg(&ctx.tv.version)
+            .arg("--install-dir")
+            .arg(&gem_home)
+            .arg("--bindir")
+            .arg(gem_home.join("bin"))
+            .arg("--no-document")
+            .arg("--clear-sources")
+            .arg("--source")
+            .arg(&settings.gem_source)
+            .with_pr(ctx.pr.as_ref())
+            .envs(ctx.ts.env_with_path(&config)?)
+            .env("GEM_HOME", &gem_home)
+            .env("GEM_PATH", &gem_home)
+            .prepend_path(ctx.ts.list_paths())?
+            .execute()?;
+
+        self.write_bin_wrappers(&ctx.tv)
+    }
+}
+
+impl GemBackend {
+    pub fn from_arg(ba: BackendArg) -> Self {
+        Self {
+            remote_version_cache: CacheManager::new(
+                ba.cache_path.join("remote_versions-$KEY.msgpack.z"),
+            ),
+            ba,
+        }
+    }
+
+    /// the executables installed by gem only work with GEM_HOME set so they are wrapped with
+    /// scripts in bin/ that set it to this version's gems
+    fn write_bin_wrappers(&self, tv: &ToolVersion) -> eyre::Result<()> {
+        let gem_home = gem_home(tv);
+        let bin_dir = tv.install_path().join("bin");
+        file::create_dir_all(&bin_dir)?;
+        for exe in file::ls(&gem_home.join("bin"))? {
+            let name = exe.file_name().unwrap().to_string_lossy().to_string();
+            let wrapper = bin_dir.join(&name);
+            let gem_home = gem_home.display();
+            let exe = exe.display();
+            file::write(
+                &wrapper,
+                format!(
+                    "#!/usr/bin/env bash\nexport GEM_HOME=\"{gem_home}\"\nexport GEM_PATH=\"{gem_home}\"\nexec \"{exe}\" \"$@\"\n"
+                ),
+            )?;
+            file::make_executable(&wrapper)?;
+        }
+        Ok(())
+    }
+}
+
+fn gem_home(tv: &ToolVersion) -> PathBuf {
+    tv.install_path().join("libexec")
+}
+
+#[derive(Debug, Deserialize)]
+struct GemVersion {
+    number: String,
+    #[serde(default)]
+    prerelease: bool,
+}
@@ -0,0 +1,261 @@
+use std::fmt::Debug;
+use std::fs::File;
+use std::path::Path;
+
+use eyre::{bail, eyre, Result};
+use flate2::read::GzDecoder;
+use serde_json::Value;
+
+use crate::backend::{Backend, BackendType};
+use crate::cache::CacheManager;
+use crate::cli::args::BackendArg;
+use crate::cli::version::{ARCH, OS};
+use crate::config::{Config, Settings};
+use crate::http::{HTTP, HTTP_FETCH};
+use crate::install_context::InstallContext;
+use crate::tera::{get_tera, BASE_CONTEXT};
+use crate::toolset::{ToolVersion, ToolVersionOptions};
+use crate::{download_cache, file, hash, lockfile};
+
+/// installs tools from arbitrary urls configured in mise.toml, e.g.:
+///
+/// ```toml
+/// [tools]
+/// "http:mytool" = { version = "1.2.3", url = "https://example.com/mytool-{{version}}-{{os}}-{{arch}}.tar.gz" }
+/// ```
+#[derive(Debug)]
+pub struct HttpBackend {
+    ba: BackendArg,
+    remote_version_cache: CacheManager<Vec<String>>,
+}
+
+impl Backend for HttpBackend {
+    fn get_type(&self) -> BackendType {
+        BackendType::Http
+    }
+
+    fn fa(&self) -> &BackendArg {
+        &self.ba
+    }
+
+    fn _list_remote_versions(&self) -> Result<Vec<String>> {
+        self.remote_version_cache
+            .get_or_try_init(|| {
+                let opts = self.config_options()?;
+                if let Some(versions) = opts.get("versions") {
+                    return Ok(versions
+                        .split(',')
+                        .map(|v| v.trim().to_string())
+                        .filter(|v| !v.is_empty())
+                        .collect());
+                }
+                match opts.get("versions_url") {
+                    Some(url) => {
+                        let path = opts.get("versions_path").map(|p| p.as_str());
+                        fetch_versions(url, path.unwrap_or("."))
+                    }
+                    None => Ok(vec![]),
+                }
+            })
+            .cloned()
+    }
+
+    fn install_version_impl(&self, ctx: &InstallContext) -> Result<()> {
+        let settings = Settings::get();
+        settings.ensure_experimental("http backend")?;
+        let opts = ctx.tv.request.options();
+        let url = match opts.get("url") {
+            Some(url) => render(url, &ctx.tv)?,
+            None => bail!("{} has no `url` option set", self.id()),
+        };
+        let filename = match url.rsplit('/').next() {
+            Some(filename) if !filename.is_empty() => filename.to_string(),
+            _ => bail!("{} url {url} does not end with a filename", self.id()),
+        };
+        let archive = ctx.tv.download_path().join(&filename);
+
+        ctx.pr.set_message(format!("downloading {filename}"));
+        download_cache::download(&url, &archive, Some(ctx.pr.as_ref()), |archive| {
+            if let Some(checksum_url) = opts.get("checksum_url") {
+                let checksum_url = render(checksum_url, &ctx.tv)?;
+                let text = HTTP.get_text(&checksum_url)?;
+                let checksum = hash::parse_checksum(&text, &filename)
+                    .ok_or_else(|| eyre!("no checksum for {filename} found in {checksum_url}"))?;
+                ctx.pr.set_message(format!("verifying {filename}"));
+                hash::ensure_checksum_sha256(archive, &checksum, Some(ctx.pr.as_ref()))?;
+            }
+            Ok(())
+        })?;
+        lockfile::record_download(&ctx.tv, &url, &archive)?;
+
+        ctx.pr.set_message(format!("installing {filename}"));
+        self.install(&ctx.tv, &opts, &archive)
+    }
+}
+
+impl HttpBackend {
+    pub fn from_arg(ba: BackendArg) -> Self {
+        Self {
+            remote_version_cache: CacheManager::new(
+                ba.cache_path.join("remote_versions-$KEY.msgpack.z"),
+            ),
+            ba,
+        }
+    }
+
+    /// options for this tool from config, remote versions are listed before there is a
+    /// ToolVersion so they can't come from there
+    fn config_options(&self) -> Result<ToolVersionOptions> {
+        let config = Config::get();
+        let trs = config.get_tool_request_set()?;
+        Ok(trs
+            .tools
+            .get(&self.ba)
+            .and_then(|trs| trs.first())
+            .map(|tr| tr.options())
+            .unwrap_or_default())
+    }
+
+    fn install(&self, tv: &ToolVersion, opts: &ToolVersionOptions, archive: &Path) -> Result<()> {
+        let install_path = tv.install_path();
+        file::remove_all(&install_path)?;
+        file::create_dir_all(&install_path)?;
+        let filename = archive
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_lowercase();
+        if filename.ends_with(".tar.gz") || filename.ends_with(".tgz") {
+            file::untar(archive, &install_path)?;
+        } else if filename.ends_with(".zip") {
+            file::unzip(archive, &install_path)?;
+        } else if regex!(r"\.(tar|tar\.xz|txz|tar\.bz2|tbz2|tar\.zst)$").is_match(&filename) {
+            file::untar_xy(archive, &install_path)?;
+        } else {
+            // a single binary, possibly gzipped
+            let bin = install_path.join("bin").join(self.name());
+            file::create_dir_all(bin.parent().unwrap())?;
+            if filename.ends_with(".gz") {
+                let mut input = GzDecoder::new(File::open(archive)?);
+                std::io::copy(&mut input, &mut file::create(&bin)?)?;
+            } else {
+                file::copy(archive, &bin)?;
+            }
+            return file::make_executable(&bin);
+        }
+        if let Some(bin_path) = opts.get("bin_path") {
+            let bin_path = install_path.join(render(bin_path, tv)?);
+            if !bin_path.is_dir() {
+                bail!("bin_path {} does not exist", file::display_path(&bin_path));
+            }
+            if bin_path != install_path.join("bin") {
+                let target = bin_path.strip_prefix(&install_path).unwrap_or(&bin_path);
+                file::make_symlink_or_file(target, &install_path.join("bin"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// renders a template from a tool option with `version`, `os` and `arch` set
+fn render(tmpl: &str, tv: &ToolVersion) -> Result<String> {
+    let mut ctx = BASE_CONTEXT.clone();
+    ctx.insert("version", &tv.version);
+    ctx.insert("os", &*OS);
+    ctx.insert("arch", &*ARCH);
+    Ok(get_tera(None).render_str(tmpl, &ctx)?)
+}
+
+fn fetch_versions(url: &str, path: &str) -> Result<Vec<String>> {
+    let json: Value = HTTP_FETCH.json(url)?;
+    json_path(&json, path)
+}
+
+/// selects values from json with a small subset of jq syntax
+/// e.g.: ".", ".versions", ".[].name", ".data.releases[].version"
+fn json_path(json: &Value, path: &str) -> Result<Vec<String>> {
+    let mut values = vec![json];
+    for segment in path.split('.').filter(|s| !s.is_empty()) {
+        let (key, flatten) = match segment.strip_suffix("[]") {
+            Some(key) => (key, true),
+            None => (segment, false),
+        };
+        if !key.is_empty() {
+            values = values.into_iter().filter_map(|v| v.get(key)).collect();
+        }
+        if flatten {
+            values = values
+                .into_iter()
+                .filter_map(|v| v.as_array())
+                .flatten()
+                .collect();
+        }
+    }
+    values
+        .into_iter()
+        .flat_map(|v| match v {
+            Value::Array(arr) => arr.iter().collect::<Vec<_>>(),
+            v => vec![v],
+        })
+        .map(|v| match v {
+            Value::String(s) => Ok(s.clone()),
+            Value::Number(n) => Ok(n.to_string()),
+            v => Err(eyre!("expected a version string at {path}, got {v}")),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_json_path() {
+        let json = json!({
+            "data": {"releases": [{"version": "1.0.0"}, {"version": "1.1.0"}]},
+            "versions": ["2.0.0", "2.1.0"],
+        });
+        assert_eq!(
+            json_path(&json, ".data.releases[].version").unwrap(),
+            vec!["1.0.0", "1.1.0"]
+        );
+        assert_eq!(
+            json_path(&json, ".versions").unwrap(),
+            vec!["2.0.0", "2.1.0"]
+        );
+        assert_eq!(json_path(&json!(["3.0.0"]), ".").unwrap(), vec!["3.0.0"]);
+        assert!(json_path(&json, ".data").is_err());
+    }
+
+    #[test]
+    fn test_fetch_versions() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/versions.json", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut req = vec![];
+            while !req.ends_with(b"\r\n\r\n") {
+                let mut buf = [0; 1];
+                stream.read_exact(&mut buf).unwrap();
+                req.push(buf[0]);
+            }
+            let body = r#"[{"name": "1.0.0"}, {"name": "1.2.0"}]"#;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+        assert_eq!(
+            fetch_versions(&url, ".[].name").unwrap(),
+            vec!["1.0.0", "1.2.0"]
+        );
+    }
+}
@@ -15,27 +15,36 @@ use strum::IntoEnumIterator;
 use versions::Versioning;
 
 use crate::cli::args::BackendArg;
+use crate::cmd::CmdLineRunner;
 use crate::config::{Config, Settings};
 use crate::file::{display_path, remove_all, remove_all_with_warning};
 use crate::install_context::InstallContext;
+use crate::install_manifest::{self, InstallManifest};
 use crate::plugins::core::CORE_PLUGINS;
-use crate::plugins::{Plugin, PluginType, VERSION_REGEX};
+use crate::plugins::{Plugin, PluginType};
 use crate::runtime_symlinks::is_runtime_symlink;
-use crate::toolset::{ToolRequest, ToolVersion, Toolset};
+use crate::toolset::{tool_usage, ToolRequest, ToolVersion, Toolset, VersionRange};
 use crate::ui::progress_report::SingleReport;
-use crate::{dirs, file, lock_file};
+use crate::{cmd, dirs, file, lock_file};
 
 use self::backend_meta::BackendMeta;
+use self::version_info::VersionInfo;
 
+pub mod aqua;
 pub mod asdf;
 pub mod backend_meta;
 pub mod cargo;
+pub mod conda;
+pub mod dotnet;
 mod external_plugin_cache;
+pub mod gem;
 pub mod go;
+pub mod http;
 pub mod npm;
 pub mod pipx;
 pub mod spm;
 pub mod ubi;
+pub mod version_info;
 pub mod vfox;
 
 pub type ABackend = Arc<dyn Backend>;
@@ -57,10 +66,15 @@ pub type BackendList = Vec<ABackend>;
 )]
 #[strum(serialize_all = "snake_case")]
 pub enum BackendType {
+    Aqua,
     Asdf,
     Cargo,
+    Conda,
     Core,
+    Dotnet,
+    Gem,
     Go,
+    Http,
     Npm,
     Pipx,
     Spm,
@@ -133,11 +147,16 @@ pub fn get(fa: &BackendArg) -> ABackend {
 
 pub fn arg_to_backend(ba: BackendArg) -> ABackend {
     match ba.backend_type {
+        BackendType::Aqua => Arc::new(aqua::AquaBackend::from_arg(ba)),
         BackendType::Asdf => Arc::new(asdf::AsdfBackend::from_arg(ba)),
         BackendType::Cargo => Arc::new(cargo::CargoBackend::from_arg(ba)),
+        BackendType::Conda => Arc::new(conda::CondaBackend::from_arg(ba)),
         BackendType::Core => Arc::new(asdf::AsdfBackend::from_arg(ba)),
         BackendType::Npm => Arc::new(npm::NPMBackend::from_arg(ba)),
+        BackendType::Dotnet => Arc::new(dotnet::DotnetBackend::from_arg(ba)),
+        BackendType::Gem => Arc::new(gem::GemBackend::from_arg(ba)),
         BackendType::Go => Arc::new(go::GoBackend::from_arg(ba)),
+        BackendType::Http => Arc::new(http::HttpBackend::from_arg(ba)),
         BackendType::Pipx => Arc::new(pipx::PIPXBackend::from_arg(ba)),
         BackendType::Spm => Arc::new(spm::SPMBackend::from_arg(ba)),
         BackendType::Ubi => Arc::new(ubi::UbiBackend::from_arg(ba)),
@@ -192,6 +211,29 @@ pub trait Backend: Debug + Send + Sync {
         self._list_remote_versions()
     }
     fn _list_remote_versions(&self) -> eyre::Result<Vec<String>>;
+    fn list_remote_versions_info(&self) -> eyre::Result<Vec<VersionInfo>> {
+        self.ensure_dependencies_installed()?;
+        trace!("Listing remote versions info for {}", self.fa().to_string());
+        self._list_remote_versions_info()
+    }
+    /// backends that know more than the version names, like release dates or which versions are
+    /// prereleases, override this instead of relying on `VersionInfo::new`
+    fn _list_remote_versions_info(&self) -> eyre::Result<Vec<VersionInfo>> {
+        Ok(self
+            ._list_remote_versions()?
+            .into_iter()
+            .map(VersionInfo::new)
+            .collect())
+    }
+    /// when each remote version was published, used to skip versions newer than
+    /// `minimum_release_age`. Versions missing from the map are never skipped.
+    fn list_remote_release_dates(&self) -> eyre::Result<BTreeMap<String, String>> {
+        Ok(self
+            .list_remote_versions_info()?
+            .into_iter()
+            .filter_map(|v| Some((v.version, v.release_date?)))
+            .collect())
+    }
     fn latest_stable_version(&self) -> eyre::Result<Option<String>> {
         self.latest_version(Some("latest".into()))
     }
@@ -202,7 +244,7 @@ pub trait Backend: Debug + Send + Sync {
                 .into_iter()
                 .filter(|v| !v.starts_with('.'))
                 .filter(|v| !is_runtime_symlink(&installs_path.join(v)))
-                .filter(|v| !installs_path.join(v).join("incomplete").exists())
+                .filter(|v| !self.fa().cache_path.join(v).join("incomplete").exists())
                 .sorted_by_cached_key(|v| (Versioning::new(v), v.to_string()))
                 .collect(),
             false => vec![],
@@ -253,10 +295,10 @@ pub trait Backend: Debug + Send + Sync {
     }
     fn list_installed_versions_matching(&self, query: &str) -> eyre::Result<Vec<String>> {
         let versions = self.list_installed_versions()?;
-        fuzzy_match_filter(versions, query)
+        fuzzy_match_filter(versions.into_iter().map(VersionInfo::new).collect(), query)
     }
     fn list_versions_matching(&self, query: &str) -> eyre::Result<Vec<String>> {
-        let versions = self.list_remote_versions()?;
+        let versions = self.list_remote_versions_info()?;
         fuzzy_match_filter(versions, query)
     }
     fn latest_version(&self, query: Option<String>) -> eyre::Result<Option<String>> {
@@ -297,6 +339,18 @@ pub trait Backend: Debug + Send + Sync {
     fn get_remote_url(&self) -> Option<String> {
         None
     }
+    /// the license of a version from its package metadata, used by `mise sbom`
+    fn license(&self, _tv: &ToolVersion) -> eyre::Result<Option<String>> {
+        Ok(None)
+    }
+    /// whether an install still works after it is moved to another directory. Relocatable
+    /// backends install into a staging dir that is renamed into place when the install is done,
+    /// so symlinks inside of the install have to be relative to keep working after the rename.
+    /// Others, e.g.: ones that compile with a prefix or write absolute paths into shebangs, install
+    /// directly into the install path with an "incomplete" marker in the cache dir.
+    fn is_relocatable(&self) -> bool {
+        true
+    }
     fn ensure_dependencies_installed(&self) -> eyre::Result<()> {
         trace!("Ensuring dependencies installed for {}", self.id());
         let deps = self
@@ -333,33 +387,60 @@ pub trait Backend: Debug + Send + Sync {
     fn plugin(&self) -> Option<&dyn Plugin> {
         None
     }
+    /// where the asdf/vfox plugin of this backend is checked out
+    fn plugin_path(&self) -> Option<PathBuf> {
+        None
+    }
 
     #[requires(ctx.tv.backend.backend_type == self.get_type())]
-    fn install_version(&self, ctx: InstallContext) -> eyre::Result<()> {
+    fn install_version(&self, mut ctx: InstallContext) -> eyre::Result<()> {
         if let Some(plugin) = self.plugin() {
             plugin.is_installed_err()?;
         }
         let config = Config::get();
         let settings = Settings::try_get()?;
+        if self.is_version_installed(&ctx.tv) && !ctx.force {
+            return Ok(());
+        }
+        // other mise processes installing the same version wait here until this one is done
+        let _lock = lock_file::get(&ctx.tv.install_path(), false)?;
         if self.is_version_installed(&ctx.tv) {
             if ctx.force {
                 self.uninstall_version(&ctx.tv, ctx.pr.as_ref(), false)?;
                 ctx.pr.set_message("installing".into());
             } else {
+                // installed by another process while waiting for the lock
                 return Ok(());
             }
         }
-        let _lock = lock_file::get(&ctx.tv.install_path(), ctx.force)?;
         self.create_install_dirs(&ctx.tv)?;
-
-        if let Err(e) = self.install_version_impl(&ctx) {
+        let tv = ctx.tv.clone();
+        ctx.tv.staged = self.is_relocatable();
+
+        let staged = ctx.tv.staged;
+        let mut result = self
+            .run_install_hook(&ctx, "preinstall")
+            .and_then(|_| self.install_version_impl(&ctx));
+        if result.is_ok() && staged {
+            // renaming is atomic so shims never see a partially installed version
+            result = file::rename(ctx.tv.install_path(), tv.install_path());
+        }
+        if result.is_ok() {
+            // after the rename so the hook sees the version where it is installed
+            ctx.tv = tv.clone();
+            result = self.run_install_hook(&ctx, "postinstall");
+        }
+        if let Err(e) = result {
             self.cleanup_install_dirs_on_error(&settings, &ctx.tv);
             return Err(e);
         }
 
-        BackendMeta::write(&ctx.tv.backend)?;
+        BackendMeta::write(&tv.backend)?;
+        if let Err(err) = InstallManifest::create(&tv).and_then(|m| m.write(&tv)) {
+            warn!("failed to write install manifest for {tv}: {err:#}");
+        }
 
-        self.cleanup_install_dirs(&settings, &ctx.tv);
+        self.cleanup_install_dirs(&settings, &tv);
         // attempt to touch all the .tool-version files to trigger updates in hook-env
         let mut touch_dirs = vec![dirs::DATA.to_path_buf()];
         touch_dirs.extend(config.config_files.keys().cloned());
@@ -369,14 +450,39 @@ pub trait Backend: Debug + Send + Sync {
                 debug!("error touching config file: {:?} {:?}", path, err);
             }
         }
-        if let Err(err) = file::remove_file(self.incomplete_file_path(&ctx.tv)) {
-            debug!("error removing incomplete file: {:?}", err);
+        if !staged {
+            if let Err(err) = file::remove_file(self.incomplete_file_path(&tv)) {
+                debug!("error removing incomplete file: {:?}", err);
+            }
         }
         ctx.pr.finish_with_message("installed".to_string());
 
         Ok(())
     }
     fn install_version_impl(&self, ctx: &InstallContext) -> eyre::Result<()>;
+    /// runs the `preinstall` or `postinstall` tool option with `cmd::inline_shell`, e.g.:
+    /// `node = { version = "22", postinstall = "corepack enable pnpm" }`
+    /// postinstall gets the bin paths and exec env of the new version, preinstall runs before
+    /// anything is installed so it only gets the env mise was started with
+    fn run_install_hook(&self, ctx: &InstallContext, hook: &str) -> eyre::Result<()> {
+        let Some(script) = ctx.tv.request.options().get(hook).cloned() else {
+            return Ok(());
+        };
+        ctx.pr.set_message(format!("{hook} {script}"));
+        let (shell, flag) = cmd::inline_shell();
+        let mut cmd = CmdLineRunner::new(shell)
+            .arg(flag)
+            .arg(&script)
+            .with_pr(ctx.pr.as_ref());
+        if hook == "postinstall" {
+            let config = Config::get();
+            cmd = cmd
+                .envs(self.exec_env(&config, ctx.ts, &ctx.tv)?)
+                .prepend_path(self.list_bin_paths(&ctx.tv)?)?;
+        }
+        cmd.execute()
+            .wrap_err_with(|| format!("{hook} failed for {}", ctx.tv))
+    }
     fn uninstall_version(
         &self,
         tv: &ToolVersion,
@@ -401,6 +507,10 @@ pub trait Backend: Debug + Send + Sync {
         rmdir(&tv.install_path())?;
         rmdir(&tv.download_path())?;
         rmdir(&tv.cache_path())?;
+        if !dryrun {
+            tool_usage::remove(tv)?;
+            install_manifest::remove(tv)?;
+        }
         Ok(())
     }
     fn uninstall_version_impl(
@@ -439,13 +549,18 @@ pub trait Backend: Debug + Send + Sync {
 
     fn create_install_dirs(&self, tv: &ToolVersion) -> eyre::Result<()> {
         let _ = remove_all_with_warning(tv.install_path());
+        let _ = remove_all_with_warning(tv.staging_path()); // left behind by an interrupted install
         let _ = remove_all_with_warning(tv.download_path());
         let _ = remove_all_with_warning(tv.cache_path());
         let _ = file::remove_file(tv.install_path()); // removes if it is a symlink
-        file::create_dir_all(tv.install_path())?;
         file::create_dir_all(tv.download_path())?;
         file::create_dir_all(tv.cache_path())?;
-        File::create(self.incomplete_file_path(tv))?;
+        if self.is_relocatable() {
+            file::create_dir_all(tv.staging_path())?;
+        } else {
+            file::create_dir_all(tv.install_path())?;
+            File::create(self.incomplete_file_path(tv))?;
+        }
         Ok(())
     }
     fn cleanup_install_dirs_on_error(&self, settings: &Settings, tv: &ToolVersion) {
@@ -478,7 +593,15 @@ pub trait Backend: Debug + Send + Sync {
     }
 }
 
-fn fuzzy_match_filter(versions: Vec<String>, query: &str) -> eyre::Result<Vec<String>> {
+fn fuzzy_match_filter(versions: Vec<VersionInfo>, query: &str) -> eyre::Result<Vec<String>> {
+    if VersionRange::is_range(query) {
+        let range = VersionRange::parse(query)?;
+        return Ok(versions
+            .into_iter()
+            .filter(|v| !v.yanked && range.matches(&v.version))
+            .map(|v| v.version)
+            .collect());
+    }
     let mut query = query;
     if query == "latest" {
         query = "v?[0-9].*";
@@ -487,14 +610,15 @@ fn fuzzy_match_filter(versions: Vec<String>, query: &str) -> eyre::Result<Vec<St
     let versions = versions
         .into_iter()
         .filter(|v| {
-            if query == v {
+            if query == v.version {
                 return true;
             }
-            if VERSION_REGEX.is_match(v) {
+            if v.is_hidden() {
                 return false;
             }
-            query_regex.is_match(v)
+            query_regex.is_match(&v.version)
         })
+        .map(|v| v.version)
         .collect();
     Ok(versions)
 }
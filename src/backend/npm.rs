@@ -1,19 +1,23 @@
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 
+use serde_derive::Deserialize;
 use serde_json::Value;
 
+use crate::backend::version_info::VersionInfo;
 use crate::backend::{Backend, BackendType};
 use crate::cache::CacheManager;
 use crate::cli::args::BackendArg;
 use crate::cmd::CmdLineRunner;
 use crate::config::{Config, Settings};
+use crate::file;
 use crate::install_context::InstallContext;
-use crate::toolset::ToolRequest;
+use crate::toolset::{ToolRequest, ToolVersion};
 
 #[derive(Debug)]
 pub struct NPMBackend {
     ba: BackendArg,
-    remote_version_cache: CacheManager<Vec<String>>,
+    remote_version_cache: CacheManager<Vec<VersionInfo>>,
     latest_version_cache: CacheManager<Option<String>>,
 }
 
@@ -31,11 +35,29 @@ impl Backend for NPMBackend {
     }
 
     fn _list_remote_versions(&self) -> eyre::Result<Vec<String>> {
+        Ok(self
+            ._list_remote_versions_info()?
+            .into_iter()
+            .map(|v| v.version)
+            .collect())
+    }
+
+    fn _list_remote_versions_info(&self) -> eyre::Result<Vec<VersionInfo>> {
         self.remote_version_cache
             .get_or_try_init(|| {
-                let raw = cmd!("npm", "view", self.name(), "versions", "--json").read()?;
-                let versions: Vec<String> = serde_json::from_str(&raw)?;
-                Ok(versions)
+                let raw = cmd!(
+                    "npm",
+                    "view",
+                    self.name(),
+                    "versions",
+                    "time",
+                    "dist-tags",
+                    "--json"
+                )
+                .full_env(self.dependency_env()?)
+                .read()?;
+                let view: NpmView = serde_json::from_str(&raw)?;
+                Ok(view.versions_info())
             })
             .cloned()
     }
@@ -74,13 +96,27 @@ impl Backend for NPMBackend {
 
         Ok(())
     }
+
+    fn license(&self, tv: &ToolVersion) -> eyre::Result<Option<String>> {
+        // `npm install -g --prefix` puts packages in lib/node_modules except on windows
+        let node_modules = match cfg!(windows) {
+            true => tv.install_path().join("node_modules"),
+            false => tv.install_path().join("lib/node_modules"),
+        };
+        let package_json = node_modules.join(self.name()).join("package.json");
+        if !package_json.exists() {
+            return Ok(None);
+        }
+        let package: Value = serde_json::from_str(&file::read_to_string(package_json)?)?;
+        Ok(package["license"].as_str().map(|l| l.to_string()))
+    }
 }
 
 impl NPMBackend {
     pub fn from_arg(ba: BackendArg) -> Self {
         Self {
             remote_version_cache: CacheManager::new(
-                ba.cache_path.join("remote_versions-$KEY.msgpack.z"),
+                ba.cache_path.join("remote_versions_info-$KEY.msgpack.z"),
             ),
             latest_version_cache: CacheManager::new(
                 ba.cache_path.join("latest_version-$KEY.msgpack.z"),
@@ -89,3 +125,63 @@ impl NPMBackend {
         }
     }
 }
+
+/// output of `npm view <package> versions time dist-tags --json`
+#[derive(Debug, Deserialize)]
+struct NpmView {
+    versions: Vec<String>,
+    /// also has "created" and "modified" keys which don't match any version
+    #[serde(default)]
+    time: BTreeMap<String, String>,
+    #[serde(default, rename = "dist-tags")]
+    dist_tags: BTreeMap<String, String>,
+}
+
+impl NpmView {
+    fn versions_info(mut self) -> Vec<VersionInfo> {
+        self.versions
+            .into_iter()
+            .map(|version| VersionInfo {
+                channel: self
+                    .dist_tags
+                    .iter()
+                    .find(|(_, v)| **v == version)
+                    .map(|(tag, _)| tag.clone()),
+                release_date: self.time.remove(&version),
+                // semver prereleases, e.g.: "2.0.0-beta.1"
+                prerelease: version.contains('-'),
+                version,
+                ..Default::default()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_versions_info() {
+        let view: NpmView = serde_json::from_str(
+            r#"{
+                "versions": ["1.0.0", "2.0.0-beta.1"],
+                "time": {"created": "2023-01-01T00:00:00.000Z", "1.0.0": "2023-01-02T00:00:00.000Z"},
+                "dist-tags": {"latest": "1.0.0", "next": "2.0.0-beta.1"}
+            }"#,
+        )
+        .unwrap();
+        let info = view.versions_info();
+        assert_eq!(info[0].channel.as_deref(), Some("latest"));
+        assert_eq!(
+            info[0].release_date.as_deref(),
+            Some("2023-01-02T00:00:00.000Z")
+        );
+        assert!(!info[0].prerelease);
+        assert_eq!(info[1].channel.as_deref(), Some("next"));
+        assert!(info[1].prerelease);
+        assert_eq!(info[1].release_date, None);
+    }
+}
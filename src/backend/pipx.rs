@@ -4,6 +4,7 @@ use std::fmt::Debug;
 use std::str::FromStr;
 use versions::Versioning;
 
+use crate::backend::version_info::VersionInfo;
 use crate::backend::{Backend, BackendType};
 use crate::cache::CacheManager;
 use crate::cli::args::BackendArg;
@@ -12,12 +13,12 @@ use crate::config::{Config, Settings};
 use crate::github;
 use crate::http::HTTP_FETCH;
 use crate::install_context::InstallContext;
-use crate::toolset::ToolRequest;
+use crate::toolset::{ToolRequest, ToolVersion};
 
 #[derive(Debug)]
 pub struct PIPXBackend {
     ba: BackendArg,
-    remote_version_cache: CacheManager<Vec<String>>,
+    remote_version_cache: CacheManager<Vec<VersionInfo>>,
     latest_version_cache: CacheManager<Option<String>>,
 }
 
@@ -34,11 +35,19 @@ impl Backend for PIPXBackend {
         Ok(vec!["pipx".into()])
     }
 
+    fn _list_remote_versions(&self) -> eyre::Result<Vec<String>> {
+        Ok(self
+            ._list_remote_versions_info()?
+            .into_iter()
+            .map(|v| v.version)
+            .collect())
+    }
+
     /*
      * Pipx doesn't have a remote version concept across its backends, so
      * we return a single version.
      */
-    fn _list_remote_versions(&self) -> eyre::Result<Vec<String>> {
+    fn _list_remote_versions_info(&self) -> eyre::Result<Vec<VersionInfo>> {
         self.remote_version_cache
             .get_or_try_init(|| match self.name().parse()? {
                 PipxRequest::Pypi(package) => {
@@ -46,18 +55,18 @@ impl Backend for PIPXBackend {
                     let data: PypiPackage = HTTP_FETCH.json(url)?;
                     let versions = data
                         .releases
-                        .keys()
-                        .map(|v| v.to_string())
-                        .sorted_by_cached_key(|v| Versioning::new(v))
+                        .into_iter()
+                        .map(|(version, files)| pypi_version_info(version, files))
+                        .sorted_by_cached_key(|v| Versioning::new(&v.version))
                         .collect();
                     Ok(versions)
                 }
                 PipxRequest::Git(url) if url.starts_with("https://github.com/") => {
                     let repo = url.strip_prefix("https://github.com/").unwrap();
                     let data = github::list_releases(repo)?;
-                    Ok(data.into_iter().map(|r| r.tag_name).collect())
+                    Ok(data.into_iter().map(VersionInfo::from).collect())
                 }
-                PipxRequest::Git { .. } => Ok(vec!["latest".to_string()]),
+                PipxRequest::Git { .. } => Ok(vec![VersionInfo::new("latest")]),
             })
             .cloned()
     }
@@ -75,6 +84,10 @@ impl Backend for PIPXBackend {
             .cloned()
     }
 
+    /// venv scripts have absolute shebangs
+    fn is_relocatable(&self) -> bool {
+        false
+    }
     fn install_version_impl(&self, ctx: &InstallContext) -> eyre::Result<()> {
         let config = Config::try_get()?;
         let settings = Settings::get();
@@ -98,13 +111,26 @@ impl Backend for PIPXBackend {
 
         Ok(())
     }
+
+    fn license(&self, tv: &ToolVersion) -> eyre::Result<Option<String>> {
+        match self.name().parse()? {
+            PipxRequest::Pypi(package) => {
+                let url = format!("https://pypi.org/pypi/{}/{}/json", package, tv.version);
+                let pkg: PypiPackageVersion = HTTP_FETCH.json(url)?;
+                let license = pkg.info.license_expression.or(pkg.info.license);
+                // some packages put the whole license text in this field
+                Ok(license.filter(|l| !l.is_empty() && !l.contains('\n')))
+            }
+            PipxRequest::Git(_) => Ok(None),
+        }
+    }
 }
 
 impl PIPXBackend {
     pub fn from_arg(ba: BackendArg) -> Self {
         Self {
             remote_version_cache: CacheManager::new(
-                ba.cache_path.join("remote_versions-$KEY.msgpack.z"),
+                ba.cache_path.join("remote_versions_info-$KEY.msgpack.z"),
             ),
             latest_version_cache: CacheManager::new(
                 ba.cache_path.join("latest_version-$KEY.msgpack.z"),
@@ -158,10 +184,41 @@ struct PypiPackage {
     info: PypiInfo,
 }
 
+/// https://pypi.org/pypi/<package>/<version>/json
+#[derive(serde::Deserialize)]
+struct PypiPackageVersion {
+    info: PypiInfo,
+}
+
 #[derive(serde::Deserialize)]
 struct PypiInfo {
     version: String,
+    #[serde(default)]
+    license: Option<String>,
+    /// PEP 639 SPDX expression, preferred over `license` when set
+    #[serde(default)]
+    license_expression: Option<String>,
 }
 
 #[derive(serde::Deserialize)]
-struct PypiRelease {}
+struct PypiRelease {
+    upload_time_iso_8601: Option<String>,
+    #[serde(default)]
+    yanked: bool,
+}
+
+fn pypi_version_info(version: String, files: Vec<PypiRelease>) -> VersionInfo {
+    VersionInfo {
+        // the first file is uploaded when the version is published
+        release_date: files
+            .iter()
+            .filter_map(|f| f.upload_time_iso_8601.clone())
+            .min(),
+        // a version is yanked on pypi by yanking all of its files
+        yanked: !files.is_empty() && files.iter().all(|f| f.yanked),
+        // pep 440 pre-releases and developmental releases, e.g.: "24.1b1" or "1.0.dev0"
+        prerelease: regex!(r"\d(a|b|rc|\.dev)\d+$").is_match(&version),
+        version,
+        ..Default::default()
+    }
+}
@@ -1,23 +1,32 @@
 use std::fmt::Debug;
+use std::fs::File;
+use std::path::{Path, PathBuf};
 
+use eyre::{bail, eyre};
+use flate2::read::GzDecoder;
+use regex::Regex;
+use walkdir::WalkDir;
+
+use crate::backend::version_info::VersionInfo;
 use crate::backend::{Backend, BackendType};
 use crate::cache::CacheManager;
 use crate::cli::args::BackendArg;
-use crate::cmd::CmdLineRunner;
-use crate::config::{Config, Settings};
-use crate::env::GITHUB_TOKEN;
-use crate::github;
+use crate::cli::version::{ARCH, OS};
+use crate::config::Settings;
+use crate::github::{GithubAsset, GithubRelease};
+use crate::http::HTTP;
 use crate::install_context::InstallContext;
-use crate::toolset::ToolRequest;
+use crate::toolset::ToolVersion;
+use crate::{download_cache, file, github, hash, http, lockfile};
 
 #[derive(Debug)]
 pub struct UbiBackend {
     ba: BackendArg,
-    remote_version_cache: CacheManager<Vec<String>>,
+    remote_version_cache: CacheManager<Vec<VersionInfo>>,
 }
 
-// Uses ubi for installations https://github.com/houseabsolute/ubi
-// it can be installed via mise install cargo:ubi
+// Installs binaries from GitHub releases, similar to ubi https://github.com/houseabsolute/ubi
+// but without needing ubi itself to be installed
 impl Backend for UbiBackend {
     fn get_type(&self) -> BackendType {
         BackendType::Ubi
@@ -27,20 +36,24 @@ impl Backend for UbiBackend {
         &self.ba
     }
 
-    fn get_dependencies(&self, _tvr: &ToolRequest) -> eyre::Result<Vec<BackendArg>> {
-        Ok(vec!["cargo:ubi".into()])
-    }
-
     // TODO: v0.0.3 is stripped of 'v' such that it reports incorrectly in tool :-/
     fn _list_remote_versions(&self) -> eyre::Result<Vec<String>> {
+        Ok(self
+            ._list_remote_versions_info()?
+            .into_iter()
+            .map(|v| v.version)
+            .collect())
+    }
+
+    fn _list_remote_versions_info(&self) -> eyre::Result<Vec<VersionInfo>> {
         if name_is_url(self.name()) {
-            Ok(vec!["latest".to_string()])
+            Ok(vec![VersionInfo::new("latest")])
         } else {
             self.remote_version_cache
                 .get_or_try_init(|| {
                     Ok(github::list_releases(self.name())?
                         .into_iter()
-                        .map(|r| r.tag_name)
+                        .map(VersionInfo::from)
                         .rev()
                         .collect())
                 })
@@ -49,31 +62,41 @@ impl Backend for UbiBackend {
     }
 
     fn install_version_impl(&self, ctx: &InstallContext) -> eyre::Result<()> {
-        let config = Config::try_get()?;
         let settings = Settings::get();
-        let version = &ctx.tv.version;
         settings.ensure_experimental("ubi backend")?;
-        // Workaround because of not knowing how to pull out the value correctly without quoting
-        let path_with_bin = ctx.tv.install_path().join("bin");
-
-        let mut cmd = CmdLineRunner::new("ubi")
-            .arg("--in")
-            .arg(path_with_bin)
-            .arg("--project")
-            .arg(self.name())
-            .with_pr(ctx.pr.as_ref())
-            .envs(ctx.ts.env_with_path(&config)?)
-            .prepend_path(ctx.ts.list_paths())?;
-
-        if let Some(token) = &*GITHUB_TOKEN {
-            cmd = cmd.env("GITHUB_TOKEN", token);
-        }
+        let release = if name_is_url(self.name()) {
+            None
+        } else {
+            Some(self.get_release(&ctx.tv.version)?)
+        };
+        let (url, checksum) = match &release {
+            None => (self.name().to_string(), None),
+            Some(release) => {
+                let asset = self.find_asset(&ctx.tv, release)?;
+                let checksum = find_checksum(release, asset)?;
+                (asset.browser_download_url.clone(), checksum)
+            }
+        };
+        let filename = url.rsplit('/').next().unwrap();
+        let archive = ctx.tv.download_path().join(filename);
 
-        if version != "latest" {
-            cmd = cmd.arg("--tag").arg(version);
-        }
+        ctx.pr.set_message(format!("downloading {filename}"));
+        download_cache::download(&url, &archive, Some(ctx.pr.as_ref()), |archive| {
+            if let Some(checksum) = &checksum {
+                ctx.pr.set_message(format!("verifying {filename}"));
+                hash::ensure_checksum_sha256(archive, checksum, Some(ctx.pr.as_ref()))?;
+            }
+            match &release {
+                Some(release) => {
+                    github::verify_cosign_bundle(self.id(), release, filename, archive)
+                }
+                None => hash::verify_signature(self.id(), archive, None),
+            }
+        })?;
+        lockfile::record_download(&ctx.tv, &url, &archive)?;
 
-        cmd.execute()
+        ctx.pr.set_message(format!("installing {filename}"));
+        self.install_bin(&ctx.tv, &archive)
     }
 }
 
@@ -81,13 +104,269 @@ impl UbiBackend {
     pub fn from_arg(ba: BackendArg) -> Self {
         Self {
             remote_version_cache: CacheManager::new(
-                ba.cache_path.join("remote_versions-$KEY.msgpack.z"),
+                ba.cache_path.join("remote_versions_info-$KEY.msgpack.z"),
             ),
             ba,
         }
     }
+
+    fn get_release(&self, version: &str) -> eyre::Result<GithubRelease> {
+        if version == "latest" {
+            return github::get_latest_release(self.name());
+        }
+        match github::get_release(self.name(), version) {
+            Err(e) if matches!(http::error_code(&e), Some(404)) && !version.starts_with('v') => {
+                github::get_release(self.name(), &format!("v{version}"))
+            }
+            r => r,
+        }
+    }
+
+    fn find_asset<'a>(
+        &self,
+        tv: &ToolVersion,
+        release: &'a GithubRelease,
+    ) -> eyre::Result<&'a GithubAsset> {
+        let asset = match tv.request.options().get("asset_pattern") {
+            Some(pattern) => {
+                let re = Regex::new(pattern)?;
+                release.assets.iter().find(|a| re.is_match(&a.name))
+            }
+            None => pick_asset(&release.assets, &OS, &ARCH),
+        };
+        asset.ok_or_else(|| {
+            let assets = release.assets.iter().map(|a| a.name.as_str());
+            eyre!(
+                "no asset found for {}-{} in {} {}\navailable assets: {}\nset `asset_pattern` to choose one",
+                *OS,
+                *ARCH,
+                self.name(),
+                release.tag_name,
+                assets.collect::<Vec<_>>().join(", ")
+            )
+        })
+    }
+
+    /// name of the binary to install, defaults to the repo name (e.g.: "goreleaser")
+    fn exe_name(&self, tv: &ToolVersion) -> String {
+        if let Some(exe) = tv.request.options().get("exe") {
+            return exe.clone();
+        }
+        let name = self
+            .name()
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .unwrap();
+        match name_is_url(self.name()) {
+            true => name.split(['_', '-', '.']).next().unwrap().to_string(),
+            false => name.to_string(),
+        }
+    }
+
+    fn install_bin(&self, tv: &ToolVersion, archive: &Path) -> eyre::Result<()> {
+        let exe = self.exe_name(tv);
+        let exe = match cfg!(windows) {
+            true => format!("{exe}.exe"),
+            false => exe,
+        };
+        let filename = archive
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_lowercase();
+        let bin_dir = tv.install_path().join("bin");
+        file::remove_all(tv.install_path())?;
+        file::create_dir_all(&bin_dir)?;
+        let extract_dir = tv.download_path().join("extracted");
+        file::remove_all(&extract_dir)?;
+        file::create_dir_all(&extract_dir)?;
+        if filename.ends_with(".tar.gz") || filename.ends_with(".tgz") {
+            file::untar(archive, &extract_dir)?;
+        } else if filename.ends_with(".zip") {
+            file::unzip(archive, &extract_dir)?;
+        } else if regex!(r"\.(tar|tar\.xz|txz|tar\.bz2|tbz|tar\.zst)$").is_match(&filename) {
+            file::untar_xy(archive, &extract_dir)?;
+        } else if filename.ends_with(".gz") {
+            let mut input = GzDecoder::new(File::open(archive)?);
+            let mut output = file::create(&bin_dir.join(&exe))?;
+            std::io::copy(&mut input, &mut output)?;
+            return file::make_executable(bin_dir.join(&exe));
+        } else {
+            file::copy(archive, bin_dir.join(&exe))?;
+            return file::make_executable(bin_dir.join(&exe));
+        }
+        let bin = find_bin(&extract_dir, &exe)?;
+        file::copy(&bin, bin_dir.join(&exe))?;
+        file::make_executable(bin_dir.join(&exe))?;
+        file::remove_all(&extract_dir)?;
+        Ok(())
+    }
 }
 
 fn name_is_url(n: &str) -> bool {
     n.starts_with("http")
 }
+
+/// finds the release asset for this os/arch by looking for common naming conventions
+/// e.g.: "goreleaser_Darwin_arm64.tar.gz" or "ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz"
+fn pick_asset<'a>(assets: &'a [GithubAsset], os: &str, arch: &str) -> Option<&'a GithubAsset> {
+    let os_re = match os {
+        "macos" => regex!(r"darwin|macos|apple|osx"),
+        "windows" => regex!(r"windows|(^|[^a-z])win(32|64)?([^a-z]|$)"),
+        _ => regex!(r"linux"),
+    };
+    let arch_re = match arch {
+        "x64" => regex!(r"x86_64|x86-64|amd64|x64"),
+        "arm64" => regex!(r"aarch64|arm64"),
+        "arm" => regex!(r"armv?7|armhf"),
+        "x86" => regex!(r"i[36]86|386"),
+        _ => return None,
+    };
+    let any_arch_re = regex!(r"x86_64|x86-64|amd64|x64|aarch64|arm64|armv?[67]|armhf|i[36]86|386");
+    let candidates = assets
+        .iter()
+        .filter(|a| !is_ignored_asset(&a.name))
+        .filter(|a| os_re.is_match(&a.name.to_lowercase()))
+        .collect::<Vec<_>>();
+    let for_arch = candidates
+        .iter()
+        .filter(|a| arch_re.is_match(&a.name.to_lowercase()));
+    // prefer statically linked builds on linux
+    let asset = for_arch
+        .clone()
+        .find(|a| a.name.to_lowercase().contains("musl"))
+        .or_else(|| for_arch.clone().next());
+    asset
+        .or_else(|| {
+            // universal binaries e.g.: "foo_darwin_all.tar.gz"
+            candidates
+                .iter()
+                .find(|a| !any_arch_re.is_match(&a.name.to_lowercase()))
+        })
+        .copied()
+}
+
+fn is_ignored_asset(name: &str) -> bool {
+    let name = name.to_lowercase();
+    regex!(r"\.(sha256|sha256sum|sha512|sig|asc|pem|sbom|json|txt|deb|rpm|apk|msi|pkg|dmg)$")
+        .is_match(&name)
+        || name.contains("checksums")
+        || name.contains("sha256sums")
+}
+
+/// looks for a checksum published alongside the asset, either as "<asset>.sha256" or inside a
+/// checksums file such as "checksums.txt" or "SHA256SUMS". It is an error for a checksums file to
+/// not list the asset.
+fn find_checksum(release: &GithubRelease, asset: &GithubAsset) -> eyre::Result<Option<String>> {
+    let sidecars = [
+        format!("{}.sha256", asset.name),
+        format!("{}.sha256sum", asset.name),
+    ];
+    if let Some(sidecar) = release.assets.iter().find(|a| sidecars.contains(&a.name)) {
+        let text = HTTP.get_text(&sidecar.browser_download_url)?;
+        return Ok(text.split_whitespace().next().map(|s| s.to_lowercase()));
+    }
+    let checksums = release.assets.iter().find(|a| {
+        let name = a.name.to_lowercase();
+        name.contains("checksums") || name.contains("sha256sums")
+    });
+    if let Some(checksums) = checksums {
+        let text = HTTP.get_text(&checksums.browser_download_url)?;
+        let shasums = hash::parse_shasums(&text);
+        let checksum = shasums
+            .iter()
+            .find(|(name, _)| name.trim_start_matches(['*', '.', '/']) == asset.name)
+            .map(|(_, checksum)| checksum.to_lowercase());
+        if checksum.is_none() {
+            bail!(
+                "{} does not contain a checksum for {}",
+                checksums.name,
+                asset.name
+            );
+        }
+        return Ok(checksum);
+    }
+    Ok(None)
+}
+
+/// finds the binary inside of an extracted archive. If there is no file named `exe` but the
+/// archive only contains one executable that is used instead.
+fn find_bin(dir: &Path, exe: &str) -> eyre::Result<PathBuf> {
+    let files = WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .collect::<Vec<_>>();
+    if let Some(bin) = files.iter().find(|f| f.file_name().unwrap() == exe) {
+        return Ok(bin.clone());
+    }
+    let executables = files
+        .into_iter()
+        .filter(|f| file::is_executable(f))
+        .collect::<Vec<_>>();
+    match executables.as_slice() {
+        [bin] => Ok(bin.clone()),
+        _ => bail!("could not find {exe} in archive, set `exe` to the name of the binary"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn assets(names: &[&str]) -> Vec<GithubAsset> {
+        names
+            .iter()
+            .map(|name| GithubAsset {
+                name: name.to_string(),
+                browser_download_url: format!("https://example.com/{name}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_pick_asset() {
+        let goreleaser = assets(&[
+            "checksums.txt",
+            "goreleaser_Darwin_all.tar.gz",
+            "goreleaser_Linux_arm64.tar.gz",
+            "goreleaser_Linux_x86_64.tar.gz",
+            "goreleaser_Linux_x86_64.tar.gz.sbom.json",
+            "goreleaser_Windows_x86_64.zip",
+            "goreleaser_1.25.0_amd64.deb",
+        ]);
+        let pick = |os, arch| pick_asset(&goreleaser, os, arch).map(|a| a.name.as_str());
+        assert_eq!(pick("linux", "x64"), Some("goreleaser_Linux_x86_64.tar.gz"));
+        assert_eq!(
+            pick("linux", "arm64"),
+            Some("goreleaser_Linux_arm64.tar.gz")
+        );
+        assert_eq!(pick("macos", "arm64"), Some("goreleaser_Darwin_all.tar.gz"));
+        assert_eq!(
+            pick("windows", "x64"),
+            Some("goreleaser_Windows_x86_64.zip")
+        );
+        assert_eq!(pick("linux", "x86"), None);
+
+        let ripgrep = assets(&[
+            "ripgrep-14.1.0-aarch64-apple-darwin.tar.gz",
+            "ripgrep-14.1.0-x86_64-apple-darwin.tar.gz",
+            "ripgrep-14.1.0-x86_64-unknown-linux-gnu.tar.gz",
+            "ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz",
+            "ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz.sha256",
+        ]);
+        let pick = |os, arch| pick_asset(&ripgrep, os, arch).map(|a| a.name.as_str());
+        assert_eq!(
+            pick("linux", "x64"),
+            Some("ripgrep-14.1.0-x86_64-unknown-linux-musl.tar.gz")
+        );
+        assert_eq!(
+            pick("macos", "arm64"),
+            Some("ripgrep-14.1.0-aarch64-apple-darwin.tar.gz")
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use serde_derive::{Deserialize, Serialize};
+
+use crate::github::GithubRelease;
+use crate::plugins::VERSION_REGEX;
+
+/// what a backend knows about a remote version, printed by `mise ls-remote --json`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VersionInfo {
+    pub version: String,
+    /// the release channel reported by the backend, e.g.: "lts" or "current" for node or "ga" for
+    /// java
+    pub channel: Option<String>,
+    /// when the version was published as an RFC 3339 timestamp or a YYYY-MM-DD date
+    pub release_date: Option<String>,
+    /// the name of the long-term support line the version belongs to, e.g.: "Iron" for node 20
+    pub lts: Option<String>,
+    /// alphas, betas, release candidates and other versions that "latest" should skip
+    pub prerelease: bool,
+    /// withdrawn by its publisher, only installed if requested exactly
+    pub yanked: bool,
+}
+
+impl VersionInfo {
+    /// a version the backend doesn't know anything else about, it is considered a prerelease if
+    /// it looks like one, e.g.: "3.13.0rc1" or "2.0.0-beta.1"
+    pub fn new(version: impl Into<String>) -> Self {
+        let version = version.into();
+        Self {
+            prerelease: VERSION_REGEX.is_match(&version),
+            version,
+            ..Default::default()
+        }
+    }
+
+    /// skipped by fuzzy matching unless requested exactly
+    pub fn is_hidden(&self) -> bool {
+        self.prerelease || self.yanked
+    }
+}
+
+impl From<GithubRelease> for VersionInfo {
+    fn from(release: GithubRelease) -> Self {
+        Self {
+            // not every project marks its release candidates as prereleases on GitHub
+            prerelease: release.prerelease || VERSION_REGEX.is_match(&release.tag_name),
+            version: release.tag_name,
+            release_date: release.published_at,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        assert!(!VersionInfo::new("3.12.1").prerelease);
+        assert!(VersionInfo::new("3.13.0rc1").prerelease);
+        assert!(VersionInfo::new("2.0.0-beta.1").is_hidden());
+        assert_eq!(
+            serde_json::to_string(&VersionInfo::new("20.11.0")).unwrap(),
+            r#"{"version":"20.11.0","channel":null,"release_date":null,"lts":null,"prerelease":false,"yanked":false}"#
+        );
+    }
+
+    #[test]
+    fn test_from_github_release() {
+        let release = |tag_name: &str, prerelease| GithubRelease {
+            tag_name: tag_name.into(),
+            prerelease,
+            published_at: Some("2024-01-09T16:12:40Z".into()),
+            assets: vec![],
+        };
+        let v = VersionInfo::from(release("v1.2.0", false));
+        assert_eq!(v.version, "v1.2.0");
+        assert_eq!(v.release_date.as_deref(), Some("2024-01-09T16:12:40Z"));
+        assert!(!v.prerelease);
+        assert!(VersionInfo::from(release("v1.3.0-rc.1", false)).prerelease);
+        assert!(VersionInfo::from(release("v1.3.0", true)).prerelease);
+    }
+}
@@ -39,6 +39,10 @@ impl Backend for VfoxBackend {
         &self.ba
     }
 
+    fn plugin_path(&self) -> Option<PathBuf> {
+        Some(self.plugin_path.clone())
+    }
+
     fn _list_remote_versions(&self) -> eyre::Result<Vec<String>> {
         self.remote_version_cache
             .get_or_try_init(|| {
@@ -55,6 +59,10 @@ impl Backend for VfoxBackend {
             .cloned()
     }
 
+    /// plugins may write the install path into the files they install
+    fn is_relocatable(&self) -> bool {
+        false
+    }
     fn install_version_impl(&self, ctx: &InstallContext) -> eyre::Result<()> {
         let settings = Settings::get();
         settings.ensure_experimental("vfox backend")?;
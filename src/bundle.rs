@@ -0,0 +1,254 @@
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use eyre::{bail, Result, WrapErr};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use path_absolutize::Absolutize;
+use serde_derive::{Deserialize, Serialize};
+use tar::{Archive, Builder, Entry, Header};
+
+use crate::backend::backend_meta::{BackendMeta, FORGE_META_FILENAME};
+use crate::backend::Backend;
+use crate::cli::args::BackendArg;
+use crate::file::display_path;
+use crate::toolset::ToolVersion;
+use crate::{dirs, file};
+
+const MANIFEST_FILENAME: &str = "manifest.json";
+
+/// an archive of installed tools that can be unpacked on a machine without network access
+///
+/// ```text
+/// manifest.json             - the tools in the bundle
+/// installs/<tool>/<version> - from dirs::INSTALLS
+/// plugins/<tool>            - asdf/vfox plugins from dirs::PLUGINS
+/// cache/<tool>              - cached remote versions from dirs::CACHE
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub tools: Vec<BundleTool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BundleTool {
+    pub short: String,
+    pub full: String,
+    pub version: String,
+}
+
+/// writes every tool version to a gzipped tarball at `path`
+pub fn create(path: &Path, versions: &[(Arc<dyn Backend>, ToolVersion)]) -> Result<BundleManifest> {
+    let mut tar = Builder::new(GzEncoder::new(file::create(path)?, Compression::default()));
+    tar.follow_symlinks(false);
+    let mut manifest = BundleManifest::default();
+    let mut seen = HashSet::new();
+    for (backend, tv) in versions {
+        let ba = backend.fa();
+        debug!("bundling {tv}");
+        let install_path = tv.install_path();
+        tar.append_dir_all(
+            archive_path("installs", &dirs::INSTALLS, &install_path)?,
+            &install_path,
+        )?;
+        if seen.insert(ba.short.clone()) {
+            append_backend(&mut tar, backend.as_ref())?;
+        }
+        manifest.tools.push(BundleTool {
+            short: ba.short.clone(),
+            full: ba.full.clone(),
+            version: tv.version.clone(),
+        });
+    }
+    let json = serde_json::to_vec_pretty(&manifest)?;
+    let mut header = Header::new_gnu();
+    header.set_size(json.len() as u64);
+    header.set_mode(0o644);
+    tar.append_data(&mut header, MANIFEST_FILENAME, json.as_slice())?;
+    tar.into_inner()?.finish()?;
+    Ok(manifest)
+}
+
+/// the files shared by all versions of a tool: its backend meta file, plugin and cache
+fn append_backend<W: std::io::Write>(tar: &mut Builder<W>, backend: &dyn Backend) -> Result<()> {
+    let ba = backend.fa();
+    let meta = ba.installs_path.join(FORGE_META_FILENAME);
+    if meta.exists() {
+        tar.append_path_with_name(&meta, archive_path("installs", &dirs::INSTALLS, &meta)?)?;
+    }
+    if let Some(plugin_path) = backend.plugin_path() {
+        if plugin_path.exists() {
+            let name = archive_path("plugins", &dirs::PLUGINS, &plugin_path)?;
+            // the .git directory isn't needed to run a plugin and is usually most of its size
+            for entry in file::ls(&plugin_path)? {
+                if entry.file_name().is_some_and(|n| n == ".git") {
+                    continue;
+                }
+                let name = name.join(entry.file_name().unwrap());
+                if entry.is_dir() && !entry.is_symlink() {
+                    tar.append_dir_all(name, &entry)?;
+                } else {
+                    tar.append_path_with_name(&entry, name)?;
+                }
+            }
+        }
+    }
+    if ba.cache_path.exists() {
+        tar.append_dir_all(
+            archive_path("cache", &dirs::CACHE, &ba.cache_path)?,
+            &ba.cache_path,
+        )?;
+    }
+    Ok(())
+}
+
+fn archive_path(prefix: &str, root: &Path, path: &Path) -> Result<PathBuf> {
+    let relative = path
+        .strip_prefix(root)
+        .wrap_err_with(|| format!("{} is not in {}", display_path(path), display_path(root)))?;
+    Ok(Path::new(prefix).join(relative))
+}
+
+/// unpacks a bundle created with `create` into dirs::INSTALLS, dirs::PLUGINS and dirs::CACHE
+/// and registers the backends of its tools
+pub fn install(path: &Path) -> Result<BundleManifest> {
+    let mut archive = Archive::new(GzDecoder::new(File::open(path)?));
+    archive.set_preserve_permissions(true);
+    archive.set_preserve_mtime(true);
+    let mut manifest = None;
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let name = entry.path()?.to_path_buf();
+        if name == Path::new(MANIFEST_FILENAME) {
+            let mut json = String::new();
+            entry.read_to_string(&mut json)?;
+            manifest = Some(serde_json::from_str::<BundleManifest>(&json)?);
+            continue;
+        }
+        if name
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            bail!("invalid path in bundle: {}", name.display());
+        }
+        let mut components = name.components();
+        let root = match components.next().and_then(|c| c.as_os_str().to_str()) {
+            Some("installs") => *dirs::INSTALLS,
+            Some("plugins") => *dirs::PLUGINS,
+            Some("cache") => *dirs::CACHE,
+            _ => {
+                warn!("skipping unexpected file in bundle: {}", name.display());
+                continue;
+            }
+        };
+        let dest = root.join(components.as_path());
+        let parent = dest.parent().unwrap_or(root);
+        file::create_dir_all(parent)?;
+        check_entry(&entry, root, parent, &name)?;
+        if !dest.is_dir() && dest.symlink_metadata().is_ok() {
+            file::remove_file(&dest)?;
+        }
+        trace!("unpacking {}", display_path(&dest));
+        entry
+            .unpack(&dest)
+            .wrap_err_with(|| format!("failed to unpack {}", display_path(&dest)))?;
+    }
+    let Some(manifest) = manifest else {
+        bail!(
+            "{} is not a mise bundle, it has no {MANIFEST_FILENAME}",
+            display_path(path)
+        );
+    };
+    for tool in &manifest.tools {
+        BackendMeta::write(&BackendArg::new(&tool.short, &tool.full))?;
+    }
+    Ok(manifest)
+}
+
+/// makes sure an entry can't write outside of `root`, either with a link pointing outside of it or
+/// through a symlink unpacked by an earlier entry
+fn check_entry<R: Read>(entry: &Entry<R>, root: &Path, parent: &Path, name: &Path) -> Result<()> {
+    if !parent.canonicalize()?.starts_with(root.canonicalize()?) {
+        bail!("invalid path in bundle: {}", name.display());
+    }
+    let entry_type = entry.header().entry_type();
+    if entry_type.is_hard_link() {
+        bail!(
+            "hard links are not supported in bundles: {}",
+            name.display()
+        );
+    }
+    if entry_type.is_symlink() {
+        let target = entry.link_name()?.unwrap_or_default();
+        let resolved = parent.join(&target);
+        if !resolved.absolutize()?.starts_with(root) {
+            bail!(
+                "symlink in bundle points outside of {}: {} -> {}",
+                display_path(root),
+                name.display(),
+                target.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use tar::EntryType;
+
+    use super::*;
+
+    #[test]
+    fn test_archive_path() {
+        assert_eq!(
+            archive_path(
+                "installs",
+                &dirs::INSTALLS,
+                &dirs::INSTALLS.join("tiny/3.1.0")
+            )
+            .unwrap(),
+            PathBuf::from("installs/tiny/3.1.0")
+        );
+        assert!(archive_path("installs", &dirs::INSTALLS, Path::new("/etc/passwd")).is_err());
+    }
+
+    #[test]
+    fn test_install_symlink_outside() {
+        let dir = tempfile::tempdir().unwrap();
+        for target in [dir.path().to_path_buf(), PathBuf::from("../../../..")] {
+            let path = dir.path().join("evil.tar.gz");
+            let mut tar = Builder::new(GzEncoder::new(
+                file::create(&path).unwrap(),
+                Compression::default(),
+            ));
+            let mut header = Header::new_gnu();
+            header.set_entry_type(EntryType::Symlink);
+            header.set_size(0);
+            header.set_mode(0o777);
+            tar.append_link(&mut header, "installs/evil/1.0.0/link", &target)
+                .unwrap();
+            let mut header = Header::new_gnu();
+            header.set_size(5);
+            header.set_mode(0o644);
+            tar.append_data(
+                &mut header,
+                "installs/evil/1.0.0/link/pwned",
+                "pwned".as_bytes(),
+            )
+            .unwrap();
+            tar.into_inner().unwrap().finish().unwrap();
+
+            let err = install(&path).unwrap_err();
+            assert!(err.to_string().contains("symlink in bundle points outside"));
+            assert!(!dir.path().join("pwned").exists());
+            assert!(!dirs::INSTALLS.join("evil/1.0.0/link").exists());
+        }
+        file::remove_all(dirs::INSTALLS.join("evil")).unwrap();
+    }
+}
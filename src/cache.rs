@@ -13,6 +13,7 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 
 use crate::build_time::built_info;
+use crate::config::Settings;
 use crate::file;
 use crate::file::{display_path, modified_duration};
 use crate::hash::hash_to_str;
@@ -120,6 +121,10 @@ where
         if !self.cache_file_path.exists() {
             return false;
         }
+        if Settings::get().offline {
+            // stale caches are better than nothing when the network can't be used
+            return true;
+        }
         if let Some(fresh_duration) = self.freshest_duration() {
             if let Ok(metadata) = self.cache_file_path.metadata() {
                 if let Ok(modified) = metadata.modified() {
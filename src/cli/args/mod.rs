@@ -2,6 +2,7 @@ pub use backend_arg::BackendArg;
 pub use cd_arg::CdArg;
 pub use env_var_arg::EnvVarArg;
 pub use log_level_arg::{DebugArg, LogLevelArg, TraceArg};
+pub use offline_arg::OfflineArg;
 pub use quiet_arg::QuietArg;
 pub use tool_arg::{ToolArg, ToolVersionType};
 pub use verbose_arg::VerboseArg;
@@ -11,6 +12,7 @@ mod backend_arg;
 mod cd_arg;
 mod env_var_arg;
 mod log_level_arg;
+mod offline_arg;
 mod quiet_arg;
 mod tool_arg;
 mod verbose_arg;
@@ -0,0 +1,13 @@
+use clap::{Arg, ArgAction};
+
+pub struct OfflineArg;
+
+impl OfflineArg {
+    pub fn arg() -> Arg {
+        Arg::new("offline")
+            .long("offline")
+            .help("Do not access the network, use cached remote versions and installed tools")
+            .action(ArgAction::SetTrue)
+            .global(true)
+    }
+}
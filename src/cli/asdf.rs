@@ -42,6 +42,7 @@ fn list_versions(config: &Config, args: &[String]) -> Result<()> {
         return LsRemote {
             prefix: None,
             all: false,
+            json: false,
             plugin: args.get(3).map(|s| s.parse()).transpose()?,
         }
         .run();
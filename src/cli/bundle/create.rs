@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+
+use clap::ValueHint;
+use eyre::{bail, Result};
+use itertools::Itertools;
+
+use crate::bundle;
+use crate::config::Config;
+use crate::file::display_path;
+use crate::toolset::ToolsetBuilder;
+
+/// Write the tools needed by the current toolset to an archive
+///
+/// The archive contains every installed version of the current tools, the asdf/vfox plugins
+/// they use and their cached remote versions. Use `mise bundle install` to unpack it on a
+/// machine without network access. Plugins are bundled without their .git directory so
+/// `mise sbom` can't report their revisions where the bundle is installed.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BundleCreate {
+    /// Path to write the archive to
+    #[clap(value_hint = ValueHint::FilePath)]
+    file: PathBuf,
+}
+
+impl BundleCreate {
+    pub fn run(self) -> Result<()> {
+        let config = Config::try_get()?;
+        let ts = ToolsetBuilder::new().build(&config)?;
+        let missing = ts.list_missing_versions();
+        if !missing.is_empty() {
+            bail!(
+                "tools are not installed: {}\nrun `mise install` before creating a bundle",
+                missing.iter().join(", ")
+            );
+        }
+        let versions = ts.list_current_installed_versions();
+        let manifest = bundle::create(&self.file, &versions)?;
+        info!(
+            "wrote {} tools to {}",
+            manifest.tools.len(),
+            display_path(&self.file)
+        );
+        Ok(())
+    }
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise bundle create mise-bundle.tar.gz</bold>
+    mise wrote 3 tools to mise-bundle.tar.gz
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use flate2::read::GzDecoder;
+    use tar::Archive;
+
+    use crate::test::reset;
+
+    #[test]
+    fn test_bundle_create() {
+        reset();
+        assert_cli!("install");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bundle.tar.gz");
+        assert_cli!("bundle", "create", path.to_str().unwrap());
+        let mut archive = Archive::new(GzDecoder::new(File::open(&path).unwrap()));
+        let entries = archive
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().to_string())
+            .collect::<Vec<_>>();
+        assert!(entries.contains(&"manifest.json".to_string()));
+        assert!(entries.iter().any(|e| e.starts_with("installs/tiny/")));
+        assert!(entries.iter().any(|e| e.starts_with("plugins/tiny/")));
+    }
+}
@@ -0,0 +1,74 @@
+use std::path::PathBuf;
+
+use clap::ValueHint;
+use eyre::Result;
+
+use crate::bundle;
+use crate::config::Config;
+use crate::file::display_path;
+
+/// Install the tools from an archive created with `mise bundle create`
+///
+/// Tool versions, plugins and cached remote versions are unpacked into the mise data and cache
+/// directories and the tools' backends are registered so no network access is needed.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct BundleInstall {
+    /// Path of the archive to install
+    #[clap(value_hint = ValueHint::FilePath)]
+    file: PathBuf,
+}
+
+impl BundleInstall {
+    pub fn run(self) -> Result<()> {
+        let manifest = bundle::install(&self.file)?;
+        for tool in &manifest.tools {
+            info!("installed {}@{}", tool.short, tool.version);
+        }
+        let config = Config::try_get()?;
+        config.rebuild_shims_and_runtime_symlinks()?;
+        info!(
+            "installed {} tools from {}",
+            manifest.tools.len(),
+            display_path(&self.file)
+        );
+        Ok(())
+    }
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise bundle install mise-bundle.tar.gz</bold>
+    mise installed node@20.11.1
+    mise installed 1 tools from mise-bundle.tar.gz
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::backend::backend_meta::BackendMeta;
+    use crate::test::reset;
+    use crate::{dirs, file};
+
+    #[test]
+    fn test_bundle_install() {
+        reset();
+        file::write(".test-tool-versions", "tiny 3.1.0\n").unwrap();
+        assert_cli!("install");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bundle.tar.gz");
+        assert_cli!("bundle", "create", path.to_str().unwrap());
+
+        assert_cli!("uninstall", "tiny@3.1.0");
+        assert!(!dirs::INSTALLS.join("tiny/3.1.0").exists());
+
+        assert_cli!("bundle", "install", path.to_str().unwrap());
+        assert!(dirs::INSTALLS.join("tiny/3.1.0/bin/rtx-tiny").exists());
+        let meta = BackendMeta::read("tiny");
+        assert_eq!(meta.short, "tiny");
+        assert_eq!(meta.backend_type, "asdf");
+        assert!(assert_cli!("where", "tiny@3.1.0").ends_with("tiny/3.1.0"));
+        file::remove_file(".test-tool-versions").unwrap();
+    }
+}
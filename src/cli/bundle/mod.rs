@@ -0,0 +1,34 @@
+use clap::Subcommand;
+use eyre::Result;
+
+mod create;
+mod install;
+
+/// Create and install archives of tools for machines without network access
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment)]
+pub struct Bundle {
+    #[clap(subcommand)]
+    command: Commands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Commands {
+    Create(create::BundleCreate),
+    Install(install::BundleInstall),
+}
+
+impl Commands {
+    pub fn run(self) -> Result<()> {
+        match self {
+            Self::Create(cmd) => cmd.run(),
+            Self::Install(cmd) => cmd.run(),
+        }
+    }
+}
+
+impl Bundle {
+    pub fn run(self) -> Result<()> {
+        self.command.run()
+    }
+}
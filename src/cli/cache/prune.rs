@@ -0,0 +1,72 @@
+use eyre::Result;
+
+use crate::config::Settings;
+use crate::download_cache;
+use crate::file::display_path;
+
+/// Removes stale files from the download cache
+///
+/// Cached downloads that haven't been used in `download_cache_max_age` are removed, then the
+/// least recently used ones until the cache is smaller than `download_cache_max_size`.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct CachePrune {
+    /// Remove downloads not used in this long, e.g.: 7d, 12h
+    #[clap(long)]
+    max_age: Option<String>,
+
+    /// Remove the least recently used downloads until the cache is this size, e.g.: 500MB, 2GiB
+    #[clap(long)]
+    max_size: Option<String>,
+
+    /// Only show what would be removed
+    #[clap(long, short = 'n')]
+    dry_run: bool,
+}
+
+impl CachePrune {
+    pub fn run(self) -> Result<()> {
+        let settings = Settings::get();
+        let max_age = self
+            .max_age
+            .unwrap_or_else(|| settings.download_cache_max_age.clone());
+        let max_size = self
+            .max_size
+            .unwrap_or_else(|| settings.download_cache_max_size.clone());
+        let max_age = humantime::parse_duration(&max_age)?;
+        let max_size = download_cache::parse_size(&max_size)?;
+        let removed = download_cache::prune(Some(max_size), Some(max_age), self.dry_run)?;
+        for download in &removed {
+            let path = display_path(&download.path);
+            if self.dry_run {
+                info!("would remove {path}");
+            } else {
+                debug!("removed {path}");
+            }
+        }
+        let freed = removed.iter().map(|d| d.size).sum::<u64>();
+        info!(
+            "pruned {} cached downloads ({})",
+            removed.len(),
+            indicatif::HumanBytes(freed)
+        );
+        Ok(())
+    }
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise cache prune</bold>
+    mise pruned 3 cached downloads (412.55 MiB)
+    $ <bold>mise cache prune --max-size 1GiB --dry-run</bold>
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_cache_prune() {
+        assert_cli!("cache", "prune", "--max-age", "0s", "--dry-run");
+    }
+}
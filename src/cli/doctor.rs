@@ -19,7 +19,7 @@ use crate::plugins::PluginType;
 use crate::shell::ShellType;
 use crate::toolset::{Toolset, ToolsetBuilder};
 use crate::ui::style;
-use crate::{backend, cmd, dirs, duration, env, file, shims};
+use crate::{backend, cmd, dirs, duration, env, eol, file, shims};
 
 /// Check mise installation for possible problems
 #[derive(Debug, clap::Args)]
@@ -158,6 +158,17 @@ impl Doctor {
             .join("\n");
 
         section("toolset", tools)?;
+
+        for (_, tv) in ts.list_current_versions() {
+            if let Ok(Some(cycle)) = eol::find_cycle(&tv) {
+                if cycle.is_eol() {
+                    self.warnings.push(format!(
+                        "{tv} is past end-of-life ({}), see `mise eol`",
+                        cycle.eol
+                    ));
+                }
+            }
+        }
         Ok(())
     }
 
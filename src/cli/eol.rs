@@ -0,0 +1,131 @@
+use eyre::Result;
+use serde_derive::Serialize;
+use tabled::{Table, Tabled};
+
+use crate::cli::args::BackendArg;
+use crate::config::Config;
+use crate::eol::{self, EolCycle};
+use crate::toolset::{ToolRequest, ToolsetBuilder};
+use crate::ui::table;
+
+/// Show the support and end-of-life dates of the current tools
+///
+/// Dates come from https://endoflife.date for node, python, ruby, go, erlang, elixir, php, kubectl
+/// and terraform and are cached for a day. Set `eol_file` to a JSON file in the same format keyed
+/// by tool name to use local data instead, e.g.: when offline.
+///
+/// `mise outdated` and `mise doctor` also warn about versions that are past end-of-life.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct Eol {
+    /// Only show these tools
+    #[clap(value_name = "TOOL")]
+    tool: Vec<BackendArg>,
+
+    /// Output in JSON format
+    #[clap(short = 'J', long)]
+    json: bool,
+
+    /// Don't show table header
+    #[clap(long)]
+    no_header: bool,
+}
+
+impl Eol {
+    pub fn run(self) -> Result<()> {
+        let config = Config::try_get()?;
+        let ts = ToolsetBuilder::new().build(&config)?;
+        let mut rows = vec![];
+        for (_, tv) in ts.list_current_versions() {
+            if matches!(tv.request, ToolRequest::System(_))
+                || (!self.tool.is_empty() && !self.tool.contains(&tv.backend))
+            {
+                continue;
+            }
+            let cycle = match eol::find_cycle(&tv) {
+                Ok(cycle) => cycle,
+                Err(err) => {
+                    warn!("failed to fetch end-of-life data for {tv}: {err:#}");
+                    None
+                }
+            };
+            rows.push(Row::new(
+                tv.backend.short.clone(),
+                tv.version.clone(),
+                cycle,
+            ));
+        }
+        if self.json {
+            miseprintln!("{}", serde_json::to_string_pretty(&rows)?);
+        } else {
+            let mut table = Table::new(rows);
+            table::default_style(&mut table, self.no_header);
+            miseprintln!("{table}");
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Tabled)]
+#[tabled(rename_all = "PascalCase")]
+struct Row {
+    tool: String,
+    version: String,
+    #[tabled(display_with = "display_option")]
+    cycle: Option<String>,
+    #[tabled(rename = "Released", display_with = "display_option")]
+    release_date: Option<String>,
+    #[tabled(display_with = "display_option")]
+    support: Option<String>,
+    #[tabled(rename = "EOL", display_with = "display_option")]
+    eol: Option<String>,
+    status: String,
+}
+
+impl Row {
+    fn new(tool: String, version: String, cycle: Option<EolCycle>) -> Self {
+        Self {
+            tool,
+            version,
+            status: cycle
+                .as_ref()
+                .map(|c| c.status().to_string())
+                .unwrap_or_else(|| "unknown".into()),
+            cycle: cycle.as_ref().map(|c| c.cycle.clone()),
+            release_date: cycle.as_ref().and_then(|c| c.release_date.clone()),
+            support: cycle
+                .as_ref()
+                .and_then(|c| c.support.as_ref().map(|s| s.to_string())),
+            eol: cycle.map(|c| c.eol.to_string()),
+        }
+    }
+}
+
+fn display_option(o: &Option<String>) -> String {
+    o.clone().unwrap_or_default()
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise eol</bold>
+    Tool    Version  Cycle  Released    Support     EOL         Status
+    node    20.11.1  20     2023-04-18  2024-10-22  2026-04-30  security_only
+    python  3.8.18   3.8    2019-10-14  2021-05-03  2024-10-07  eol
+
+    $ <bold>mise eol python --json</bold>
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use crate::test::reset;
+
+    #[test]
+    fn test_eol() {
+        reset();
+        // tiny has no end-of-life data
+        let output = assert_cli!("eol", "tiny");
+        assert!(output.contains("unknown"));
+    }
+}
@@ -13,7 +13,7 @@ use crate::cli::args::ToolArg;
 use crate::cmd;
 use crate::config::Config;
 use crate::env;
-use crate::toolset::{InstallOptions, ToolsetBuilder};
+use crate::toolset::{tool_usage, InstallOptions, ToolsetBuilder};
 
 /// Execute a command with tool(s) set
 ///
@@ -63,9 +63,14 @@ impl Exec {
             jobs: self.jobs,
             raw: self.raw,
             latest_versions: false,
+            locked: false,
         };
         ts.install_arg_versions(&config, &opts)?;
         ts.notify_if_versions_missing();
+        if !*env::__MISE_SHIM {
+            // shims only record the version of the tool they run
+            tool_usage::record_toolset(&ts);
+        }
 
         let (program, args) = parse_command(&env::SHELL, &self.command, &self.c);
         let env = ts.env_with_path(&config)?;
@@ -11,7 +11,7 @@ use crate::direnv::DirenvDiff;
 use crate::env::{PATH_KEY, TERM_WIDTH, __MISE_DIFF};
 use crate::env_diff::{EnvDiff, EnvDiffOperation};
 use crate::shell::{get_shell, ShellType};
-use crate::toolset::{Toolset, ToolsetBuilder};
+use crate::toolset::{tool_usage, Toolset, ToolsetBuilder};
 use crate::{env, hook_env};
 
 /// [internal] called by activate hook to update env vars directory change
@@ -39,6 +39,7 @@ impl HookEnv {
             return Ok(());
         }
         let ts = ToolsetBuilder::new().build(&config)?;
+        tool_usage::record_toolset(&ts);
         let shell = get_shell(self.shell).expect("no shell provided, use `--shell=zsh`");
         miseprint!("{}", hook_env::clear_old_env(&*shell))?;
         let mut env = ts.env(&config)?;
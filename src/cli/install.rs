@@ -1,9 +1,10 @@
-use eyre::Result;
+use eyre::{bail, Result};
 use itertools::Itertools;
 use std::collections::HashSet;
 
 use crate::cli::args::{BackendArg, ToolArg};
 use crate::config::Config;
+use crate::lockfile;
 use crate::toolset::{InstallOptions, ToolRequest, ToolVersion, ToolVersionOptions, Toolset};
 use crate::ui::multi_progress_report::MultiProgressReport;
 
@@ -38,6 +39,10 @@ pub struct Install {
     #[clap(long, overrides_with = "jobs")]
     raw: bool,
 
+    /// Fail instead of resolving versions that are not already in mise.lock
+    #[clap(long)]
+    locked: bool,
+
     /// Show installation output
     ///
     /// This argument will print plugin output such as download, configuration, and compilation output.
@@ -48,6 +53,9 @@ pub struct Install {
 impl Install {
     pub fn run(self) -> Result<()> {
         let config = Config::try_get()?;
+        if self.locked && !lockfile::is_enabled() {
+            bail!("--locked requires the `lockfile` and `experimental` settings to be enabled");
+        }
         match &self.tool {
             Some(runtime) => self.install_runtimes(&config, runtime)?,
             None => self.install_missing_runtimes(&config)?,
@@ -65,7 +73,9 @@ impl Install {
             warn!("specify a version with `mise install <PLUGIN>@<VERSION>`");
             return Ok(vec![]);
         }
-        ts.install_versions(config, tool_versions, &mpr, &self.install_opts())
+        let versions = ts.install_versions(config, tool_versions, &mpr, &self.install_opts())?;
+        lockfile::update_lockfiles(&ts)?;
+        Ok(versions)
     }
 
     fn install_opts(&self) -> InstallOptions {
@@ -74,6 +84,7 @@ impl Install {
             jobs: self.jobs,
             raw: self.raw,
             latest_versions: true,
+            locked: self.locked,
         }
     }
 
@@ -126,7 +137,9 @@ impl Install {
         }
         let mpr = MultiProgressReport::get();
         let mut ts = Toolset::from(trs.clone());
-        ts.install_versions(config, versions, &mpr, &self.install_opts())
+        let versions = ts.install_versions(config, versions, &mpr, &self.install_opts())?;
+        lockfile::update_lockfiles(&ts)?;
+        Ok(versions)
     }
 }
 
@@ -144,8 +157,8 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
 mod tests {
     use pretty_assertions::assert_str_eq;
 
-    use crate::dirs;
     use crate::test::reset;
+    use crate::{dirs, env, file};
 
     #[test]
     fn test_install_force() {
@@ -179,6 +192,44 @@ mod tests {
         assert_cli!("global", "--unset", "dummy");
     }
 
+    #[test]
+    fn test_install_locked() {
+        reset();
+        env::set_var("MISE_EXPERIMENTAL", "1");
+        env::set_var("MISE_LOCKFILE", "1");
+        file::write(".test.mise.toml", "[tools]\ntiny = \"3\"\n").unwrap();
+        // loading the config does not write the lockfile
+        assert_cli!("ls");
+        assert!(!env::current_dir().unwrap().join("mise.lock").exists());
+
+        let err = assert_cli_err!("install", "--locked", "-f", "tiny");
+        assert!(format!("{err:?}").contains("tiny@3 is not in mise.lock"));
+
+        assert_cli!("install", "tiny");
+        let lockfile = file::read_to_string("mise.lock").unwrap();
+        assert!(lockfile.contains("version = \"3.1.0\""));
+        assert_cli!("install", "--locked", "-f", "tiny");
+        env::remove_var("MISE_EXPERIMENTAL");
+        env::remove_var("MISE_LOCKFILE");
+    }
+
+    #[test]
+    fn test_install_hooks() {
+        reset();
+        file::write(
+            ".test.mise.toml",
+            r#"[tools]
+tiny = { version = "3.1.0", preinstall = "echo pre > hooks.txt", postinstall = "rtx-tiny post >> hooks.txt" }
+"#,
+        )
+        .unwrap();
+        assert_cli!("install", "-f", "tiny");
+        assert_str_eq!(
+            file::read_to_string("hooks.txt").unwrap(),
+            "pre\nrtx-tiny: v3.1.0 args: post\n"
+        );
+    }
+
     #[test]
     fn test_install_nothing() {
         reset();
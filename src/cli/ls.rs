@@ -3,6 +3,7 @@ use std::fmt::{Display, Formatter};
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use chrono::{DateTime, SecondsFormat, Utc};
 use console::style;
 use eyre::{ensure, Result};
 use indexmap::IndexMap;
@@ -14,7 +15,7 @@ use versions::Versioning;
 use crate::backend::Backend;
 use crate::cli::args::BackendArg;
 use crate::config::Config;
-use crate::toolset::{ToolSource, ToolVersion, Toolset};
+use crate::toolset::{tool_usage, ToolSource, ToolVersion, Toolset};
 use crate::ui::table;
 use crate::{backend, config};
 
@@ -251,6 +252,9 @@ struct JSONToolVersion {
     symlinked_to: Option<PathBuf>,
     installed: bool,
     active: bool,
+    /// when the version was last used by a shim, `mise exec` or `hook-env`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_used: Option<String>,
 }
 
 type RuntimeRow = (Arc<dyn Backend>, ToolVersion, Option<ToolSource>);
@@ -289,14 +293,20 @@ impl From<RuntimeRow> for JSONToolVersion {
     fn from(row: RuntimeRow) -> Self {
         let (p, tv, source) = row;
         let vs: VersionStatus = (p.as_ref(), &tv, &source).into();
+        let installed = !matches!(vs, VersionStatus::Missing(_));
+        let last_used = installed
+            .then(|| tool_usage::last_used(&tv))
+            .flatten()
+            .map(|t| DateTime::<Utc>::from(t).to_rfc3339_opts(SecondsFormat::Secs, true));
         JSONToolVersion {
             symlinked_to: p.symlink_path(&tv),
             install_path: tv.install_path(),
             version: tv.version,
             requested_version: source.as_ref().map(|_| tv.request.version()),
             source: source.map(|source| source.as_json()),
-            installed: !matches!(vs, VersionStatus::Missing(_)),
+            installed,
             active: matches!(vs, VersionStatus::Active(_, _)),
+            last_used,
         }
     }
 }
@@ -448,8 +458,12 @@ mod tests {
         reset();
         let _ = remove_all(*dirs::INSTALLS);
         assert_cli!("install");
-        assert_cli_snapshot!("ls", "--json");
-        assert_cli_snapshot!("ls", "--json", "tiny");
+        insta::with_settings!({filters => vec![
+            (r#""last_used": "[^"]+""#, r#""last_used": "[last_used]""#),
+        ]}, {
+            assert_cli_snapshot!("ls", "--json");
+            assert_cli_snapshot!("ls", "--json", "tiny");
+        });
     }
 
     #[test]
@@ -1,10 +1,12 @@
 use std::sync::Arc;
 
 use eyre::Result;
+use indexmap::IndexMap;
 use itertools::Itertools;
 use rayon::prelude::*;
 
 use crate::backend;
+use crate::backend::version_info::VersionInfo;
 use crate::backend::Backend;
 use crate::cli::args::ToolArg;
 use crate::toolset::ToolRequest;
@@ -30,6 +32,12 @@ pub struct LsRemote {
     /// same as the first argument after the "@"
     #[clap(verbatim_doc_comment)]
     pub prefix: Option<String>,
+
+    /// Output in JSON format
+    /// includes the channel, release date, lts name and whether the version is a prerelease or
+    /// yanked if the backend knows them
+    #[clap(short = 'J', long, verbatim_doc_comment)]
+    pub json: bool,
 }
 
 impl LsRemote {
@@ -50,17 +58,28 @@ impl LsRemote {
             _ => self.prefix.clone(),
         };
 
-        let versions = plugin.list_remote_versions()?;
+        let versions = match self.json {
+            true => plugin.list_remote_versions_info()?,
+            false => plugin
+                .list_remote_versions()?
+                .into_iter()
+                .map(VersionInfo::new)
+                .collect(),
+        };
         let versions = match prefix {
             Some(prefix) => versions
                 .into_iter()
-                .filter(|v| v.starts_with(&prefix))
+                .filter(|v| v.version.starts_with(&prefix))
                 .collect(),
             None => versions,
         };
 
+        if self.json {
+            miseprintln!("{}", serde_json::to_string_pretty(&versions)?);
+            return Ok(());
+        }
         for version in versions {
-            miseprintln!("{}", version);
+            miseprintln!("{}", version.version);
         }
 
         Ok(())
@@ -70,16 +89,31 @@ impl LsRemote {
         let versions = backend::list()
             .into_par_iter()
             .map(|p| {
-                let versions = p.list_remote_versions()?;
+                let versions = match self.json {
+                    true => p.list_remote_versions_info()?,
+                    false => p
+                        .list_remote_versions()?
+                        .into_iter()
+                        .map(VersionInfo::new)
+                        .collect(),
+                };
                 Ok((p, versions))
             })
             .collect::<Result<Vec<_>>>()?
             .into_iter()
             .sorted_by_cached_key(|(p, _)| p.id().to_string())
             .collect::<Vec<_>>();
+        if self.json {
+            let versions = versions
+                .into_iter()
+                .map(|(p, versions)| (p.id().to_string(), versions))
+                .collect::<IndexMap<_, _>>();
+            miseprintln!("{}", serde_json::to_string_pretty(&versions)?);
+            return Ok(());
+        }
         for (plugin, versions) in versions {
             for v in versions {
-                miseprintln!("{}@{v}", plugin);
+                miseprintln!("{}@{}", plugin, v.version);
             }
         }
         Ok(())
@@ -114,6 +148,19 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
     $ <bold>mise ls-remote node 20</bold>
     20.0.0
     20.1.0
+
+    $ <bold>mise ls-remote node@20 --json</bold>
+    [
+      {
+        "version": "20.0.0",
+        "channel": "current",
+        "release_date": "2023-04-18",
+        "lts": null,
+        "prerelease": false,
+        "yanked": false
+      },
+      ...
+    ]
 "#
 );
 
@@ -129,4 +176,9 @@ mod tests {
         assert_cli_snapshot!("list-remote", "dummy", "1");
         assert_cli_snapshot!("list-remote", "dummy@2");
     }
+
+    #[test]
+    fn test_ls_remote_json() {
+        assert_cli_snapshot!("list-remote", "dummy@2", "--json");
+    }
 }
@@ -11,6 +11,7 @@ pub mod args;
 mod asdf;
 pub mod backends;
 mod bin_paths;
+mod bundle;
 mod cache;
 mod completion;
 mod config;
@@ -19,6 +20,7 @@ mod deactivate;
 mod direnv;
 mod doctor;
 mod env;
+mod eol;
 pub mod exec;
 mod external;
 mod generate;
@@ -42,6 +44,7 @@ mod render_help;
 mod render_mangen;
 mod reshim;
 mod run;
+mod sbom;
 mod self_update;
 mod set;
 mod settings;
@@ -54,6 +57,7 @@ mod unset;
 mod upgrade;
 mod usage;
 mod r#use;
+mod verify;
 pub mod version;
 mod watch;
 mod r#where;
@@ -68,6 +72,7 @@ pub enum Commands {
     Asdf(asdf::Asdf),
     Backends(backends::Backends),
     BinPaths(bin_paths::BinPaths),
+    Bundle(bundle::Bundle),
     Cache(cache::Cache),
     Completion(completion::Completion),
     Config(config::Config),
@@ -76,6 +81,7 @@ pub enum Commands {
     Direnv(direnv::Direnv),
     Doctor(doctor::Doctor),
     Env(env::Env),
+    Eol(eol::Eol),
     Exec(exec::Exec),
     Generate(generate::Generate),
     Global(global::Global),
@@ -94,6 +100,7 @@ pub enum Commands {
     Registry(registry::Registry),
     Reshim(reshim::Reshim),
     Run(run::Run),
+    Sbom(sbom::Sbom),
     SelfUpdate(self_update::SelfUpdate),
     Set(set::Set),
     Settings(settings::Settings),
@@ -106,6 +113,7 @@ pub enum Commands {
     Upgrade(upgrade::Upgrade),
     Usage(usage::Usage),
     Use(r#use::Use),
+    Verify(verify::Verify),
     Version(version::Version),
     Watch(watch::Watch),
     Where(r#where::Where),
@@ -126,6 +134,7 @@ impl Commands {
             Self::Asdf(cmd) => cmd.run(),
             Self::Backends(cmd) => cmd.run(),
             Self::BinPaths(cmd) => cmd.run(),
+            Self::Bundle(cmd) => cmd.run(),
             Self::Cache(cmd) => cmd.run(),
             Self::Completion(cmd) => cmd.run(),
             Self::Config(cmd) => cmd.run(),
@@ -134,6 +143,7 @@ impl Commands {
             Self::Direnv(cmd) => cmd.run(),
             Self::Doctor(cmd) => cmd.run(),
             Self::Env(cmd) => cmd.run(),
+            Self::Eol(cmd) => cmd.run(),
             Self::Exec(cmd) => cmd.run(),
             Self::Generate(cmd) => cmd.run(),
             Self::Global(cmd) => cmd.run(),
@@ -152,6 +162,7 @@ impl Commands {
             Self::Registry(cmd) => cmd.run(),
             Self::Reshim(cmd) => cmd.run(),
             Self::Run(cmd) => cmd.run(),
+            Self::Sbom(cmd) => cmd.run(),
             Self::SelfUpdate(cmd) => cmd.run(),
             Self::Set(cmd) => cmd.run(),
             Self::Settings(cmd) => cmd.run(),
@@ -164,6 +175,7 @@ impl Commands {
             Self::Upgrade(cmd) => cmd.run(),
             Self::Usage(cmd) => cmd.run(),
             Self::Use(cmd) => cmd.run(),
+            Self::Verify(cmd) => cmd.run(),
             Self::Version(cmd) => cmd.run(),
             Self::Watch(cmd) => cmd.run(),
             Self::Where(cmd) => cmd.run(),
@@ -192,6 +204,7 @@ impl Cli {
                 .arg(args::CdArg::arg())
                 .arg(args::DebugArg::arg())
                 .arg(args::LogLevelArg::arg())
+                .arg(args::OfflineArg::arg())
                 .arg(args::QuietArg::arg())
                 .arg(args::TraceArg::arg())
                 .arg(args::VerboseArg::arg())
@@ -7,7 +7,8 @@ use eyre::Result;
 use crate::backend::Backend;
 use crate::cli::args::ToolArg;
 use crate::config::Config;
-use crate::toolset::{ToolVersion, ToolsetBuilder};
+use crate::eol;
+use crate::toolset::{ToolVersion, Toolset, ToolsetBuilder};
 
 /// Shows outdated tool versions
 #[derive(Debug, clap::Args)]
@@ -22,6 +23,11 @@ pub struct Outdated {
     /// Output in JSON format
     #[clap(short = 'J', long, verbatim_doc_comment)]
     pub json: bool,
+
+    /// Compare against the latest version even if it doesn't match the requested prefix
+    /// e.g.: node@20 is outdated if node 22 is out, `mise upgrade --bump` will update the config
+    #[clap(long, verbatim_doc_comment)]
+    pub bump: bool,
 }
 
 impl Outdated {
@@ -35,7 +41,18 @@ impl Outdated {
             .collect::<HashSet<_>>();
         ts.versions
             .retain(|_, tvl| tool_set.is_empty() || tool_set.contains(&tvl.backend));
-        let outdated = ts.list_outdated_versions();
+        let outdated = match self.bump {
+            true => ts
+                .list_bumped_versions()
+                .into_iter()
+                .map(|(t, tv, latest, bump)| (t, tv, latest, Some(bump)))
+                .collect::<OutputVec>(),
+            false => ts
+                .list_outdated_versions()
+                .into_iter()
+                .map(|(t, tv, latest)| (t, tv, latest, None))
+                .collect::<OutputVec>(),
+        };
         if outdated.is_empty() {
             info!("All tools are up to date");
         } else if self.json {
@@ -43,20 +60,24 @@ impl Outdated {
         } else {
             self.display(outdated)?;
         }
+        warn_eol(&ts);
 
         Ok(())
     }
 
     fn display(&self, outdated: OutputVec) -> Result<()> {
         // TODO: make a generic table printer in src/ui/table
-        let plugins = outdated.iter().map(|(t, _, _)| t.id()).collect::<Vec<_>>();
+        let plugins = outdated
+            .iter()
+            .map(|(t, _, _, _)| t.id())
+            .collect::<Vec<_>>();
         let requests = outdated
             .iter()
-            .map(|(_, tv, _)| tv.request.version())
+            .map(|(_, tv, _, _)| tv.request.version())
             .collect::<Vec<_>>();
         let currents = outdated
             .iter()
-            .map(|(t, tv, _)| {
+            .map(|(t, tv, _, _)| {
                 if t.is_version_installed(tv) {
                     tv.version.clone()
                 } else {
@@ -66,8 +87,15 @@ impl Outdated {
             .collect::<Vec<_>>();
         let latests = outdated
             .iter()
-            .map(|(_, _, c)| c.clone())
+            .map(|(_, _, c, _)| c.clone())
             .collect::<Vec<_>>();
+        let latest_width = latests
+            .iter()
+            .map(|s| s.len())
+            .max()
+            .unwrap_or_default()
+            .max(6)
+            + 1;
         let plugin_width = plugins
             .iter()
             .map(|s| s.len())
@@ -92,32 +120,57 @@ impl Outdated {
         let pad_plugin = |s| pad_str(s, plugin_width, Alignment::Left, None);
         let pad_requested = |s| pad_str(s, requested_width, Alignment::Left, None);
         let pad_current = |s| pad_str(s, current_width, Alignment::Left, None);
-        miseprintln!(
-            "{} {} {} {}",
-            style(pad_plugin("Tool")).dim(),
-            style(pad_requested("Requested")).dim(),
-            style(pad_current("Current")).dim(),
-            style("Latest").dim(),
-        );
-        for i in 0..outdated.len() {
+        let pad_latest = |s| pad_str(s, latest_width, Alignment::Left, None);
+        if self.bump {
+            miseprintln!(
+                "{} {} {} {} {}",
+                style(pad_plugin("Tool")).dim(),
+                style(pad_requested("Requested")).dim(),
+                style(pad_current("Current")).dim(),
+                style(pad_latest("Latest")).dim(),
+                style("Bump").dim(),
+            );
+        } else {
             miseprintln!(
                 "{} {} {} {}",
-                pad_plugin(plugins[i]),
-                pad_requested(&requests[i]),
-                pad_current(&currents[i]),
-                latests[i]
+                style(pad_plugin("Tool")).dim(),
+                style(pad_requested("Requested")).dim(),
+                style(pad_current("Current")).dim(),
+                style("Latest").dim(),
             );
         }
+        for (i, (_, _, _, bump)) in outdated.iter().enumerate() {
+            match bump {
+                Some(bump) => miseprintln!(
+                    "{} {} {} {} {}",
+                    pad_plugin(plugins[i]),
+                    pad_requested(&requests[i]),
+                    pad_current(&currents[i]),
+                    pad_latest(&latests[i]),
+                    bump
+                ),
+                None => miseprintln!(
+                    "{} {} {} {}",
+                    pad_plugin(plugins[i]),
+                    pad_requested(&requests[i]),
+                    pad_current(&currents[i]),
+                    latests[i]
+                ),
+            }
+        }
         Ok(())
     }
 
     fn display_json(&self, outdated: OutputVec) -> Result<()> {
         let mut map = serde_json::Map::new();
-        for (t, tv, c) in outdated {
+        for (t, tv, c, bump) in outdated {
             let mut inner = serde_json::Map::new();
             inner.insert("requested".to_string(), tv.request.version().into());
             inner.insert("current".to_string(), tv.version.clone().into());
             inner.insert("latest".to_string(), c.into());
+            if let Some(bump) = bump {
+                inner.insert("bump".to_string(), bump.into());
+            }
             map.insert(t.id().to_string(), serde_json::Value::Object(inner));
         }
         let json = serde_json::Value::Object(map);
@@ -126,22 +179,42 @@ impl Outdated {
     }
 }
 
-type OutputVec = Vec<(Arc<dyn Backend>, ToolVersion, String)>;
+/// warns about current versions that are past end-of-life since updating within the requested
+/// prefix won't help with those
+fn warn_eol(ts: &Toolset) {
+    for (_, tv) in ts.list_current_versions() {
+        match eol::find_cycle(&tv) {
+            Ok(Some(cycle)) if cycle.is_eol() => {
+                warn!("{tv} is past end-of-life ({}), see `mise eol`", cycle.eol)
+            }
+            Ok(_) => {}
+            Err(err) => debug!("failed to fetch end-of-life data for {tv}: {err:#}"),
+        }
+    }
+}
+
+/// (tool, current version, latest version, request to bump to with --bump)
+type OutputVec = Vec<(Arc<dyn Backend>, ToolVersion, String, Option<String>)>;
 
 static AFTER_LONG_HELP: &str = color_print::cstr!(
     r#"<bold><underline>Examples:</underline></bold>
 
     $ <bold>mise outdated</bold>
-    Plugin  Requested  Current  Latest
+    Tool    Requested  Current  Latest
     python  3.11       3.11.0   3.11.1
     node    20         20.0.0   20.1.0
 
     $ <bold>mise outdated node</bold>
-    Plugin  Requested  Current  Latest
+    Tool    Requested  Current  Latest
     node    20         20.0.0   20.1.0
 
     $ <bold>mise outdated --json</bold>
     {"python": {"requested": "3.11", "current": "3.11.0", "latest": "3.11.1"}, ...}
+
+    $ <bold>mise outdated --bump</bold>
+    Tool    Requested  Current  Latest  Bump
+    python  3.11       3.11.0   3.12.4  3.12
+    node    20         20.0.0   22.3.0  22
 "#
 );
 
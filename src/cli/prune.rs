@@ -3,12 +3,13 @@ use std::sync::Arc;
 
 use console::style;
 use eyre::Result;
+use itertools::Itertools;
 
 use crate::backend::Backend;
 use crate::cli::args::BackendArg;
 use crate::config::tracking::Tracker;
 use crate::config::{Config, Settings};
-use crate::toolset::{ToolVersion, Toolset, ToolsetBuilder};
+use crate::toolset::{tool_usage, ToolVersion, Toolset, ToolsetBuilder};
 use crate::ui::multi_progress_report::MultiProgressReport;
 use crate::ui::prompt;
 
@@ -20,6 +21,10 @@ use super::trust::Trust;
 /// Versions which are no longer the latest specified in any of those configs are deleted.
 /// Versions installed only with environment variables (`MISE_<PLUGIN>_VERSION`) will be deleted,
 /// as will versions only referenced on the command line (`mise exec <PLUGIN>@<VERSION>`).
+///
+/// Shims, `mise exec` and `mise activate` record when each version was last used. With
+/// --unused-for, versions that haven't been used in that long are deleted even if a config
+/// still references them.
 #[derive(Debug, clap::Args)]
 #[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
 pub struct Prune {
@@ -38,6 +43,14 @@ pub struct Prune {
     /// Prune only unused versions of tools
     #[clap(long)]
     pub tools: bool,
+
+    /// Also delete versions referenced by configs if they haven't been used in this long, e.g.: 90d
+    #[clap(long, value_name = "DURATION")]
+    pub unused_for: Option<String>,
+
+    /// Keep this many of the most recently used versions of each tool that --unused-for would delete
+    #[clap(long, value_name = "COUNT", requires = "unused_for")]
+    pub keep: Option<usize>,
 }
 
 impl Prune {
@@ -63,11 +76,21 @@ impl Prune {
     }
 
     fn prune_tools(&self) -> Result<()> {
+        let to_delete = self.get_unused_versions()?;
+        self.delete(to_delete)
+    }
+
+    fn get_unused_versions(&self) -> Result<Vec<(Arc<dyn Backend>, ToolVersion)>> {
         let config = Config::try_get()?;
         let ts = ToolsetBuilder::new().build(&config)?;
-        let mut to_delete = ts
-            .list_installed_versions()?
-            .into_iter()
+        let installed = ts.list_installed_versions()?;
+        let unused_for = match &self.unused_for {
+            Some(d) => Some(humantime::parse_duration(d)?),
+            None => None,
+        };
+        let mut to_delete = installed
+            .iter()
+            .cloned()
             .map(|(p, tv)| (tv.to_string(), (p, tv)))
             .collect::<BTreeMap<String, (Arc<dyn Backend>, ToolVersion)>>();
 
@@ -75,15 +98,37 @@ impl Prune {
             to_delete.retain(|_, (_, tv)| backends.contains(&tv.backend));
         }
 
+        // versions that configs reference but are only deleted because of --unused-for
+        let mut stale = BTreeMap::new();
         for cf in config.get_tracked_config_files()?.values() {
             let mut ts = Toolset::from(cf.to_tool_request_set()?);
             ts.resolve()?;
             for (_, tv) in ts.list_current_versions() {
+                if unused_for.is_some_and(|d| tool_usage::is_unused_for(&tv, d)) {
+                    stale.insert(tv.to_string(), tv);
+                    continue;
+                }
                 to_delete.remove(&tv.to_string());
             }
         }
 
-        self.delete(to_delete.into_values().collect())
+        if let Some(keep) = self.keep {
+            let by_tool = stale
+                .into_values()
+                .filter(|tv| to_delete.contains_key(&tv.to_string()))
+                .into_group_map_by(|tv| tv.backend.to_string());
+            for tvs in by_tool.into_values() {
+                let recent = tvs
+                    .into_iter()
+                    .sorted_by_key(|tv| std::cmp::Reverse(tool_usage::last_used(tv)))
+                    .take(keep);
+                for tv in recent {
+                    to_delete.remove(&tv.to_string());
+                }
+            }
+        }
+
+        Ok(to_delete.into_values().collect())
     }
 
     fn delete(&self, to_delete: Vec<(Arc<dyn Backend>, ToolVersion)>) -> Result<()> {
@@ -110,14 +155,26 @@ static AFTER_LONG_HELP: &str = color_print::cstr!(
     $ <bold>mise prune --dry-run</bold>
     rm -rf ~/.local/share/mise/versions/node/20.0.0
     rm -rf ~/.local/share/mise/versions/node/20.0.1
+
+    # also delete configured versions not used in 90 days but keep the 2 most recently used of those per tool
+    $ <bold>mise prune --unused-for 90d --keep 2</bold>
 "#
 );
 
 #[cfg(test)]
 mod tests {
-    use crate::test::reset;
+    use std::time::{Duration, SystemTime};
+
+    use filetime::{set_file_mtime, FileTime};
+    use pretty_assertions::assert_eq;
     use test_log::test;
 
+    use crate::cli::Cli;
+    use crate::test::reset;
+    use crate::{dirs, file};
+
+    use super::Prune;
+
     #[test]
     fn test_prune() {
         reset();
@@ -126,4 +183,58 @@ mod tests {
         assert_cli!("prune");
         assert_cli!("install");
     }
+
+    #[test]
+    fn test_prune_unused_for() {
+        reset();
+        assert_cli!("install");
+        assert_cli!("install", "tiny@1.0.0", "tiny@1.1.0", "tiny@2.1.0");
+        // without a usage record the install time is used as the last use
+        file::remove_all(dirs::TOOL_USAGE.join("tiny")).unwrap();
+        let used = |version: &str, days: u64| {
+            let time = SystemTime::now() - Duration::from_secs(days * 24 * 60 * 60);
+            let path = dirs::INSTALLS.join("tiny").join(version);
+            set_file_mtime(path, FileTime::from_system_time(time)).unwrap();
+        };
+        used("1.0.0", 200);
+        used("1.1.0", 10);
+        used("2.1.0", 100);
+        let pruned = |unused_for: Option<&str>, keep: Option<usize>| {
+            let prune = Prune {
+                plugin: Some(vec!["tiny".into()]),
+                dry_run: true,
+                configs: false,
+                tools: true,
+                unused_for: unused_for.map(String::from),
+                keep,
+            };
+            prune
+                .get_unused_versions()
+                .unwrap()
+                .into_iter()
+                .map(|(_, tv)| tv.version)
+                // other tests may have installed 3.1.0 which isn't referenced here
+                .filter(|v| v != "3.1.0")
+                .collect::<Vec<_>>()
+        };
+        // 2.1.0 is referenced by the test configs
+        assert_eq!(pruned(None, None), vec!["1.0.0", "1.1.0"]);
+        assert_eq!(pruned(Some("90d"), None), vec!["1.0.0", "1.1.0", "2.1.0"]);
+        // --keep only spares versions --unused-for would delete, not the more recent 1.1.0
+        assert_eq!(pruned(Some("90d"), Some(1)), vec!["1.0.0", "1.1.0"]);
+        assert_eq!(
+            pruned(Some("90d"), Some(0)),
+            vec!["1.0.0", "1.1.0", "2.1.0"]
+        );
+        assert!(Cli::command()
+            .try_get_matches_from(["mise", "prune", "--keep", "1"])
+            .is_err());
+
+        used("2.1.0", 0);
+        assert_cli!("prune", "tiny");
+        assert!(!dirs::INSTALLS.join("tiny/1.0.0").exists());
+        assert!(!dirs::INSTALLS.join("tiny/1.1.0").exists());
+        assert!(dirs::INSTALLS.join("tiny/2.1.0").exists());
+        assert_cli!("uninstall", "tiny@2.1.0");
+    }
 }
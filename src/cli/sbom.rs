@@ -0,0 +1,96 @@
+use std::path::PathBuf;
+
+use chrono::{SecondsFormat, Utc};
+use clap::ValueHint;
+use eyre::Result;
+
+use crate::config::Config;
+use crate::file::display_path;
+use crate::toolset::ToolsetBuilder;
+use crate::{file, sbom};
+
+/// Generate a software bill of materials for the current tools
+///
+/// Lists the current version of every tool with its backend and, when they are known, its
+/// download url, checksum and license. Tools installed with asdf/vfox plugins also list the
+/// plugin's git repo and the revision it is checked out at.
+///
+/// Download urls and checksums come from mise.lock so they are only included for tools in a
+/// lockfile. Licenses come from npm, pypi and crates.io package metadata.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct Sbom {
+    /// SBOM format to output. Default is cyclonedx.
+    #[clap(long, short)]
+    format: Option<SbomFormat>,
+
+    /// Write the SBOM to this file instead of stdout
+    #[clap(long, short, value_hint = ValueHint::FilePath)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SbomFormat {
+    Cyclonedx,
+    Spdx,
+}
+
+impl Sbom {
+    pub fn run(self) -> Result<()> {
+        let config = Config::try_get()?;
+        let ts = ToolsetBuilder::new().build(&config)?;
+        let tools = sbom::collect(&ts)?;
+        let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        let doc = match self.format.unwrap_or(SbomFormat::Cyclonedx) {
+            SbomFormat::Cyclonedx => sbom::cyclonedx(&tools, &timestamp),
+            SbomFormat::Spdx => sbom::spdx(&tools, &timestamp),
+        };
+        let json = serde_json::to_string_pretty(&doc)?;
+        match &self.output {
+            Some(path) => {
+                file::write(path, json + "\n")?;
+                info!("wrote {} tools to {}", tools.len(), display_path(path));
+            }
+            None => miseprintln!("{json}"),
+        }
+        Ok(())
+    }
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise sbom > sbom.cdx.json</bold>
+    $ <bold>mise sbom --format spdx --output sbom.spdx.json</bold>
+    mise wrote 3 tools to sbom.spdx.json
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde_json::Value;
+
+    use crate::test::reset;
+
+    #[test]
+    fn test_sbom() {
+        reset();
+        let output = assert_cli!("sbom");
+        let bom: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(bom["bomFormat"], "CycloneDX");
+        let components = bom["components"].as_array().unwrap();
+        assert!(components.iter().any(|c| c["bom-ref"] == "tiny@3.1.0"));
+        assert!(components.iter().any(|c| c["bom-ref"] == "plugin:tiny"));
+    }
+
+    #[test]
+    fn test_sbom_spdx() {
+        reset();
+        let output = assert_cli!("sbom", "--format", "spdx");
+        let doc: Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(doc["spdxVersion"], "SPDX-2.3");
+        let packages = doc["packages"].as_array().unwrap();
+        assert!(packages.iter().any(|p| p["SPDXID"] == "SPDXRef-tiny-3.1.0"));
+    }
+}
@@ -63,29 +63,43 @@ mod tests {
         all_compile = false
         always_keep_download = true
         always_keep_install = true
+        aqua_registry_url = "https://github.com/aquaproj/aqua-registry"
         asdf = true
         asdf_compat = false
+        bun_skip_checksum = false
         cargo_binstall = true
         color = true
+        conda_channel = "https://conda.anaconda.org/conda-forge"
+        deno_skip_checksum = false
         disable_default_shorthands = false
         disable_tools = []
+        dotnet_nuget_source = "https://api.nuget.org/v3/index.json"
+        download_cache = true
+        download_cache_max_age = "30d"
+        download_cache_max_size = "10GiB"
+        eol_url = "https://endoflife.date/api"
         experimental = true
+        gem_source = "https://rubygems.org"
         go_default_packages_file = "~/.default-go-packages"
         go_download_mirror = "https://dl.google.com/go"
         go_repo = "https://github.com/golang/go"
         go_set_gopath = false
         go_set_goroot = true
         go_skip_checksum = false
+        http_retries = 3
         http_timeout = 30
         jobs = 2
         legacy_version_file = true
         legacy_version_file_disable_tools = []
         libgit2 = true
+        lockfile = false
         node_compile = false
         not_found_auto_install = true
+        offline = false
         paranoid = false
         plugin_autoupdate_last_check_duration = "20m"
         python_default_packages_file = "~/.default-python-packages"
+        python_precompiled_skip_checksum = false
         python_pyenv_repo = "https://github.com/pyenv/pyenv.git"
         quiet = false
         raw = false
@@ -109,29 +123,43 @@ mod tests {
         all_compile
         always_keep_download
         always_keep_install
+        aqua_registry_url
         asdf
         asdf_compat
+        bun_skip_checksum
         cargo_binstall
         color
+        conda_channel
+        deno_skip_checksum
         disable_default_shorthands
         disable_tools
+        dotnet_nuget_source
+        download_cache
+        download_cache_max_age
+        download_cache_max_size
+        eol_url
         experimental
+        gem_source
         go_default_packages_file
         go_download_mirror
         go_repo
         go_set_gopath
         go_set_goroot
         go_skip_checksum
+        http_retries
         http_timeout
         jobs
         legacy_version_file
         legacy_version_file_disable_tools
         libgit2
+        lockfile
         node_compile
         not_found_auto_install
+        offline
         paranoid
         plugin_autoupdate_last_check_duration
         python_default_packages_file
+        python_precompiled_skip_checksum
         python_pyenv_repo
         quiet
         raw
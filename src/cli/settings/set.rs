@@ -24,13 +24,26 @@ impl SettingsSet {
             "all_compile" => parse_bool(&self.value)?,
             "always_keep_download" => parse_bool(&self.value)?,
             "always_keep_install" => parse_bool(&self.value)?,
+            "aqua_registry_dir" => self.value.into(),
+            "aqua_registry_url" => self.value.into(),
             "asdf" => parse_bool(&self.value)?,
             "asdf_compat" => parse_bool(&self.value)?,
+            "bun_skip_checksum" => parse_bool(&self.value)?,
+            "ca_bundle" => self.value.into(),
             "cargo_binstall" => parse_bool(&self.value)?,
             "color" => parse_bool(&self.value)?,
+            "conda_channel" => self.value.into(),
+            "deno_skip_checksum" => parse_bool(&self.value)?,
             "disable_default_shorthands" => parse_bool(&self.value)?,
             "disable_tools" => self.value.split(',').map(|s| s.to_string()).collect(),
+            "dotnet_nuget_source" => self.value.into(),
+            "download_cache" => parse_bool(&self.value)?,
+            "download_cache_max_age" => self.value.into(),
+            "download_cache_max_size" => self.value.into(),
+            "eol_file" => self.value.into(),
+            "eol_url" => self.value.into(),
             "experimental" => parse_bool(&self.value)?,
+            "gem_source" => self.value.into(),
             "go_default_packages_file" => self.value.into(),
             "go_download_mirror" => self.value.into(),
             "go_repo" => self.value.into(),
@@ -38,6 +51,7 @@ impl SettingsSet {
             "go_set_gopath" => parse_bool(&self.value)?,
             "go_set_goroot" => parse_bool(&self.value)?,
             "go_skip_checksum" => parse_bool(&self.value)?,
+            "http_retries" => parse_i64(&self.value)?,
             "http_timeout" => parse_i64(&self.value)?,
             "jobs" => parse_i64(&self.value)?,
             "legacy_version_file" => parse_bool(&self.value)?,
@@ -45,12 +59,16 @@ impl SettingsSet {
                 self.value.split(',').map(|s| s.to_string()).collect()
             }
             "libgit2" => parse_bool(&self.value)?,
+            "lockfile" => parse_bool(&self.value)?,
+            "minimum_release_age" => self.value.into(),
             "node_compile" => parse_bool(&self.value)?,
             "not_found_auto_install" => parse_bool(&self.value)?,
+            "offline" => parse_bool(&self.value)?,
             "paranoid" => parse_bool(&self.value)?,
             "plugin_autoupdate_last_check_duration" => self.value.into(),
             "python_compile" => parse_bool(&self.value)?,
             "python_default_packages_file" => self.value.into(),
+            "python_precompiled_skip_checksum" => parse_bool(&self.value)?,
             "python_pyenv_repo" => self.value.into(),
             "python_venv_auto_create" => parse_bool(&self.value)?,
             "quiet" => parse_bool(&self.value)?,
@@ -137,29 +155,43 @@ pub mod tests {
         all_compile = false
         always_keep_download = true
         always_keep_install = true
+        aqua_registry_url = "https://github.com/aquaproj/aqua-registry"
         asdf = true
         asdf_compat = false
+        bun_skip_checksum = false
         cargo_binstall = true
         color = true
+        conda_channel = "https://conda.anaconda.org/conda-forge"
+        deno_skip_checksum = false
         disable_default_shorthands = false
         disable_tools = []
+        dotnet_nuget_source = "https://api.nuget.org/v3/index.json"
+        download_cache = true
+        download_cache_max_age = "30d"
+        download_cache_max_size = "10GiB"
+        eol_url = "https://endoflife.date/api"
         experimental = true
+        gem_source = "https://rubygems.org"
         go_default_packages_file = "~/.default-go-packages"
         go_download_mirror = "https://dl.google.com/go"
         go_repo = "https://github.com/golang/go"
         go_set_gopath = false
         go_set_goroot = true
         go_skip_checksum = false
+        http_retries = 3
         http_timeout = 30
         jobs = 2
         legacy_version_file = false
         legacy_version_file_disable_tools = []
         libgit2 = true
+        lockfile = false
         node_compile = false
         not_found_auto_install = true
+        offline = false
         paranoid = false
         plugin_autoupdate_last_check_duration = "1"
         python_default_packages_file = "~/.default-python-packages"
+        python_precompiled_skip_checksum = false
         python_pyenv_repo = "https://github.com/pyenv/pyenv.git"
         quiet = false
         raw = false
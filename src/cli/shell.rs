@@ -50,6 +50,7 @@ impl Shell {
             jobs: self.jobs,
             raw: self.raw,
             latest_versions: false,
+            locked: false,
         };
         ts.install_arg_versions(&config, &opts)?;
         ts.notify_if_versions_missing();
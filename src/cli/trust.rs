@@ -18,6 +18,7 @@ use crate::file::{display_path, remove_file};
 /// - environment variables
 /// - templates
 /// - `path:` plugin versions
+/// - `preinstall` and `postinstall` tool options
 #[derive(Debug, clap::Args)]
 #[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
 pub struct Trust {
@@ -1,13 +1,20 @@
 use std::collections::HashSet;
+use std::path::PathBuf;
 use std::sync::Arc;
 
+use console::style;
 use demand::DemandOption;
 use eyre::{Context, Result};
+use indexmap::IndexMap;
+use itertools::Itertools;
 
 use crate::backend::Backend;
-use crate::cli::args::ToolArg;
-use crate::config::Config;
-use crate::toolset::{InstallOptions, ToolVersion, ToolsetBuilder};
+use crate::cli::args::{BackendArg, ToolArg};
+use crate::config::{config_file, Config};
+use crate::file::display_path;
+use crate::toolset::{
+    InstallOptions, ToolRequest, ToolSource, ToolVersion, Toolset, ToolsetBuilder,
+};
 use crate::ui::multi_progress_report::MultiProgressReport;
 use crate::ui::progress_report::SingleReport;
 use crate::{runtime_symlinks, shims, ui};
@@ -39,12 +46,25 @@ pub struct Upgrade {
     /// Sets --jobs=1
     #[clap(long, overrides_with = "jobs")]
     raw: bool,
+
+    /// Upgrade to the latest version even if it doesn't match the requested prefix
+    /// and update the config files to request it with the same precision
+    /// e.g.: node@20 becomes node@22 and python@3.11 becomes python@3.12
+    #[clap(long, verbatim_doc_comment)]
+    bump: bool,
+
+    /// Output the bumped tools in JSON format
+    #[clap(short = 'J', long, verbatim_doc_comment, requires = "bump")]
+    json: bool,
 }
 
 impl Upgrade {
     pub fn run(self) -> Result<()> {
         let config = Config::try_get()?;
         let ts = ToolsetBuilder::new().with_args(&self.tool).build(&config)?;
+        if self.bump {
+            return self.run_bump(&config, &ts);
+        }
         let mut outdated = ts.list_outdated_versions();
         if self.interactive && !outdated.is_empty() {
             let tvs = self.get_interactive_tool_set(&outdated)?;
@@ -99,6 +119,7 @@ impl Upgrade {
             jobs: self.jobs,
             raw: self.raw,
             latest_versions: true,
+            locked: false,
         };
         let new_versions = new_versions.into_iter().map(|tv| tv.request).collect();
         ts.install_versions(config, new_versions, &mpr, &opts)?;
@@ -113,6 +134,123 @@ impl Upgrade {
         Ok(())
     }
 
+    fn run_bump(&self, config: &Config, ts: &Toolset) -> Result<()> {
+        let mut bumped = ts.list_bumped_versions();
+        if self.interactive && !bumped.is_empty() {
+            let outdated = bumped
+                .iter()
+                .map(|(t, tv, _, bump)| (t.clone(), tv.clone(), bump.clone()))
+                .collect::<OutputVec>();
+            let tvs = self.get_interactive_tool_set(&outdated)?;
+            bumped.retain(|(_, tv, _, _)| tvs.contains(tv));
+        } else {
+            let tool_set = self
+                .tool
+                .iter()
+                .map(|t| t.backend.clone())
+                .collect::<HashSet<_>>();
+            bumped.retain(|(p, _, _, _)| tool_set.is_empty() || tool_set.contains(p.fa()));
+        }
+
+        // the versions each config file should request for each tool, in the original order
+        let mut requests: IndexMap<PathBuf, IndexMap<BackendArg, Vec<String>>> = IndexMap::new();
+        let mut changes = vec![];
+        for (tool, tv, latest, bump) in bumped {
+            let Some(tvl) = ts.versions.get(&tv.backend) else {
+                continue;
+            };
+            let path = match &tvl.source {
+                ToolSource::MiseToml(path) | ToolSource::ToolVersions(path) => path.clone(),
+                source => {
+                    warn!("{tv} is set by {source} which can't be bumped");
+                    continue;
+                }
+            };
+            let versions = requests
+                .entry(path.clone())
+                .or_default()
+                .entry(tv.backend.clone())
+                .or_insert_with(|| tvl.requests.iter().map(|r| r.version()).collect());
+            for v in versions.iter_mut().filter(|v| **v == tv.request.version()) {
+                v.clone_from(&bump);
+            }
+            changes.push((tool, tv, latest, bump, path));
+        }
+        if changes.is_empty() {
+            info!("All tools are up to date");
+            return Ok(());
+        }
+
+        for (path, tools) in requests {
+            let mut cf = config_file::parse(&path)?;
+            let before = cf.dump()?;
+            for (fa, versions) in tools {
+                cf.replace_versions(&fa, &versions.into_iter().unique().collect_vec())?;
+            }
+            if !self.json {
+                let path = display_path(&path);
+                miseprintln!("{}", style(format!("--- {path}")).bold());
+                miseprintln!("{}", style(format!("+++ {path}")).bold());
+                for line in diff_lines(&before, &cf.dump()?) {
+                    match line.starts_with('-') {
+                        true => miseprintln!("{}", style(line).red()),
+                        false => miseprintln!("{}", style(line).green()),
+                    }
+                }
+            }
+            if !self.dry_run {
+                cf.save()?;
+            }
+        }
+        if self.json {
+            let json = changes
+                .iter()
+                .map(|(tool, tv, latest, bump, path)| {
+                    serde_json::json!({
+                        "tool": tool.id(),
+                        "path": path,
+                        "requested": tv.request.version(),
+                        "bump": bump,
+                        "current": tv.version,
+                        "latest": latest,
+                    })
+                })
+                .collect_vec();
+            miseprintln!("{}", serde_json::to_string_pretty(&json)?);
+        }
+        if self.dry_run {
+            return Ok(());
+        }
+
+        let mpr = MultiProgressReport::get();
+        let new_versions = changes
+            .iter()
+            .map(|(_, tv, _, bump, _)| {
+                ToolRequest::new_opts(tv.backend.clone(), bump, tv.request.options())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let opts = InstallOptions {
+            force: false,
+            jobs: self.jobs,
+            raw: self.raw,
+            latest_versions: true,
+            locked: false,
+        };
+        let mut ts = ToolsetBuilder::new().with_args(&self.tool).build(config)?;
+        ts.install_versions(config, new_versions, &mpr, &opts)?;
+        for (tool, tv, _, _, _) in changes {
+            if tool.is_version_installed(&tv) {
+                let pr = mpr.add(&tv.style());
+                self.uninstall_old_version(tool.clone(), &tv, pr.as_ref())?;
+            }
+        }
+
+        let ts = ToolsetBuilder::new().with_args(&self.tool).build(config)?;
+        shims::reshim(&ts).wrap_err("failed to reshim")?;
+        runtime_symlinks::rebuild(config)?;
+        Ok(())
+    }
+
     fn uninstall_old_version(
         &self,
         tool: Arc<dyn Backend>,
@@ -145,10 +283,45 @@ impl Upgrade {
 
 type OutputVec = Vec<(Arc<dyn Backend>, ToolVersion, String)>;
 
+/// the lines removed from `old` prefixed with "-" and the lines added in `new` prefixed with "+"
+fn diff_lines(old: &str, new: &str) -> Vec<String> {
+    let old = old.lines().collect_vec();
+    let new = new.lines().collect_vec();
+    // lcs[i][j] is the length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = match old[i] == new[j] {
+                true => lcs[i + 1][j + 1] + 1,
+                false => lcs[i + 1][j].max(lcs[i][j + 1]),
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut lines = vec![];
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            lines.push(format!("-{}", old[i]));
+            i += 1;
+        } else {
+            lines.push(format!("+{}", new[j]));
+            j += 1;
+        }
+    }
+    lines
+}
+
 #[cfg(test)]
 pub mod tests {
-    use crate::dirs;
+    use pretty_assertions::assert_eq;
+
     use crate::test::{change_installed_version, reset};
+    use crate::{dirs, file};
+
+    use super::diff_lines;
 
     #[test]
     fn test_upgrade() {
@@ -158,4 +331,38 @@ pub mod tests {
         assert_cli_snapshot!("upgrade");
         assert!(dirs::INSTALLS.join("tiny").join("3.1.0").exists());
     }
+
+    #[test]
+    fn test_upgrade_bump() {
+        reset();
+        file::write(".test.mise.toml", "[tools]\ntiny = \"2\"\n").unwrap();
+        let json = assert_cli!("upgrade", "--bump", "--dry-run", "--json", "tiny");
+        let json: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(json[0]["tool"], "tiny");
+        assert_eq!(json[0]["requested"], "2");
+        assert_eq!(json[0]["bump"], "3");
+        assert_eq!(json[0]["latest"], "3.1.0");
+        assert_eq!(
+            file::read_to_string(".test.mise.toml").unwrap(),
+            "[tools]\ntiny = \"2\"\n"
+        );
+
+        assert_cli!("upgrade", "--bump", "tiny");
+        assert_eq!(
+            file::read_to_string(".test.mise.toml").unwrap(),
+            "[tools]\ntiny = \"3\"\n"
+        );
+        assert!(dirs::INSTALLS.join("tiny").join("3.1.0").exists());
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let old = "[tools]\nnode = \"20\"\npython = \"3.11\"\n";
+        let new = "[tools]\nnode = \"22\"\npython = \"3.11\"\n";
+        assert_eq!(
+            diff_lines(old, new),
+            vec![r#"-node = "20""#, r#"+node = "22""#]
+        );
+        assert!(diff_lines(old, old).is_empty());
+    }
 }
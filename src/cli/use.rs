@@ -11,9 +11,11 @@ use crate::env::{
     MISE_DEFAULT_CONFIG_FILENAME, MISE_DEFAULT_TOOL_VERSIONS_FILENAME, MISE_GLOBAL_CONFIG_FILE,
 };
 use crate::file::display_path;
-use crate::toolset::{InstallOptions, ToolRequest, ToolSource, ToolVersion, ToolsetBuilder};
+use crate::toolset::{
+    InstallOptions, ToolRequest, ToolSource, ToolVersion, Toolset, ToolsetBuilder,
+};
 use crate::ui::multi_progress_report::MultiProgressReport;
-use crate::{env, file};
+use crate::{env, file, lockfile};
 
 /// Install tool version and add it to config
 ///
@@ -102,6 +104,7 @@ impl Use {
                 jobs: self.jobs,
                 raw: self.raw,
                 latest_versions: false,
+                locked: false,
             },
         )?;
 
@@ -130,6 +133,11 @@ impl Use {
             cf.remove_plugin(plugin_name)?;
         }
         cf.save()?;
+        if lockfile::is_enabled() {
+            let mut ts = Toolset::from(cf.to_tool_request_set()?);
+            ts.resolve()?;
+            lockfile::update_lockfiles(&ts)?;
+        }
         self.render_success_message(cf.as_ref(), &versions)?;
         Ok(())
     }
@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+
+use eyre::{bail, Result};
+
+use crate::cli::args::ToolArg;
+use crate::config::Config;
+use crate::install_manifest::InstallManifest;
+use crate::toolset::ToolsetBuilder;
+
+/// Check installed tools for modified, missing or extra files
+///
+/// mise writes a manifest with the sha256 of every file in an install directory after installing
+/// a tool. This compares the files in the install directory with it to find toolchains that were
+/// corrupted or tampered with. Packages installed into a tool after it was installed, e.g.: with
+/// `npm install -g`, are reported as extra files.
+#[derive(Debug, clap::Args)]
+#[clap(verbatim_doc_comment, after_long_help = AFTER_LONG_HELP)]
+pub struct Verify {
+    /// Tool(s) to verify
+    /// e.g.: node@20 python@3.10
+    /// If not specified, all current tools are verified
+    #[clap(value_name = "TOOL@VERSION", verbatim_doc_comment)]
+    tool: Vec<ToolArg>,
+}
+
+impl Verify {
+    pub fn run(self) -> Result<()> {
+        let config = Config::try_get()?;
+        let mut ts = ToolsetBuilder::new().with_args(&self.tool).build(&config)?;
+        let tool_set = self
+            .tool
+            .iter()
+            .map(|t| t.backend.clone())
+            .collect::<HashSet<_>>();
+        ts.versions
+            .retain(|_, tvl| tool_set.is_empty() || tool_set.contains(&tvl.backend));
+        let mut failed = 0;
+        for (_, tv) in ts.list_current_installed_versions() {
+            let manifest = match InstallManifest::read(&tv)? {
+                Some(manifest) => manifest,
+                None => {
+                    warn!("{tv} has no install manifest, reinstall it with `mise install -f {tv}`");
+                    continue;
+                }
+            };
+            let verification = manifest.verify(&tv)?;
+            if verification.is_ok() {
+                miseprintln!("{tv}: ok");
+                continue;
+            }
+            failed += 1;
+            miseprintln!(
+                "{tv}: {} modified, {} missing, {} extra",
+                verification.modified.len(),
+                verification.missing.len(),
+                verification.extra.len()
+            );
+            for path in &verification.modified {
+                miseprintln!("  modified {path}");
+            }
+            for path in &verification.missing {
+                miseprintln!("  missing  {path}");
+            }
+            for path in &verification.extra {
+                miseprintln!("  extra    {path}");
+            }
+        }
+        if failed > 0 {
+            bail!("{failed} tool version(s) failed verification");
+        }
+        Ok(())
+    }
+}
+
+static AFTER_LONG_HELP: &str = color_print::cstr!(
+    r#"<bold><underline>Examples:</underline></bold>
+
+    $ <bold>mise verify</bold>
+    node@20.11.1: ok
+    python@3.12.1: 1 modified, 0 missing, 1 extra
+      modified bin/python3.12
+      extra    bin/evil
+    mise ERROR 1 tool version(s) failed verification
+
+    $ <bold>mise verify node@20</bold>
+    node@20.11.1: ok
+"#
+);
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_str_eq;
+
+    use crate::test::{cli_run, reset};
+    use crate::{dirs, file};
+
+    #[test]
+    fn test_verify() {
+        reset();
+        assert_cli!("install", "-f", "tiny");
+        let output = assert_cli!("verify", "tiny");
+        assert_str_eq!(output, "tiny@3.1.0: ok");
+
+        let bin = dirs::INSTALLS.join("tiny/3.1.0/bin/rtx-tiny");
+        let original = file::read_to_string(&bin).unwrap();
+        file::write(&bin, "tampered").unwrap();
+        assert!(cli_run(&vec!["mise".into(), "verify".into(), "tiny".into()]).is_err());
+        file::write(&bin, original).unwrap();
+    }
+}
@@ -105,6 +105,15 @@ where
     }
 }
 
+/// the shell and its flag that the `preinstall` and `postinstall` install hooks are run with
+pub fn inline_shell() -> (&'static str, &'static str) {
+    if cfg!(windows) {
+        ("cmd.exe", "/c")
+    } else {
+        ("sh", "-c")
+    }
+}
+
 pub struct CmdLineRunner<'a> {
     cmd: Command,
     pr: Option<&'a dyn SingleReport>,
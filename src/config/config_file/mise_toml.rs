@@ -13,6 +13,7 @@ use tera::Context as TeraContext;
 use toml_edit::{table, value, Array, DocumentMut, Item, Value};
 use versions::Versioning;
 
+use crate::backend::BackendType;
 use crate::cli::args::{BackendArg, ToolVersionType};
 use crate::config::config_file::toml::deserialize_arr;
 use crate::config::config_file::{trust_check, ConfigFile, TaskConfig};
@@ -324,10 +325,18 @@ impl ConfigFile for MiseToml {
                 if let ToolVersionType::Path(_) = &tool.tt {
                     trust_check(&self.path)?;
                 }
+                if tool.options.contains_key("preinstall")
+                    || tool.options.contains_key("postinstall")
+                {
+                    trust_check(&self.path)?;
+                }
                 let version = self.parse_template(&tool.tt.to_string())?;
                 let mut options = tool.options.clone();
-                for v in options.values_mut() {
-                    *v = self.parse_template(v)?;
+                // http backend options are templates rendered at install time with the version
+                if fa.backend_type != BackendType::Http {
+                    for v in options.values_mut() {
+                        *v = self.parse_template(v)?;
+                    }
                 }
                 let tvr = ToolRequest::new_opts(fa.clone(), &version, options)?;
                 trs.add_version(tvr, &source);
@@ -1139,6 +1148,32 @@ mod tests {
         file::remove_all(&p).unwrap();
     }
 
+    #[test]
+    fn test_range() {
+        reset();
+        let p = PathBuf::from("/tmp/.test-range.mise.toml");
+        let orig = formatdoc! {r#"
+            [tools]
+            python = ">=3.10,<3.13"
+            node = "^20.11"
+            java = ">=17,<22"
+            "#};
+        file::write(&p, &orig).unwrap();
+        let cf = MiseToml::from_file(&p).unwrap();
+        let versions = cf
+            .to_tool_request_set()
+            .unwrap()
+            .iter()
+            .map(|(ba, trs, _)| format!("{ba}@{}", trs[0].version()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            versions,
+            vec!["python@>=3.10,<3.13", "node@^20.11", "java@>=17,<22"]
+        );
+        assert_eq!(cf.dump().unwrap(), orig);
+        file::remove_all(&p).unwrap();
+    }
+
     #[test]
     fn test_remove_plugin() {
         reset();
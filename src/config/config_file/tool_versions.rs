@@ -247,6 +247,25 @@ pub(crate) mod tests {
         assert_eq!(tv.dump().unwrap(), orig);
     }
 
+    #[test]
+    fn test_parse_range() {
+        reset();
+        let orig = indoc! {"
+        python >=3.10,<3.13
+        ruby   ~3.2
+        "};
+        let path = env::current_dir().unwrap().join(".test-tool-versions");
+        let tv = ToolVersions::parse_str(orig, path).unwrap();
+        assert_eq!(tv.dump().unwrap(), orig);
+        let versions = tv
+            .to_tool_request_set()
+            .unwrap()
+            .iter()
+            .map(|(_, tvl, _)| tvl.iter().map(|tr| tr.version()).join(" "))
+            .collect_vec();
+        assert_eq!(versions, vec![">=3.10,<3.13", "~3.2"]);
+    }
+
     #[test]
     fn test_parse_colon() {
         reset();
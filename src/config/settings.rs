@@ -8,6 +8,7 @@ use std::sync::{Arc, Mutex, RwLock};
 use confique::env::parse::{list_by_colon, list_by_comma};
 use confique::{Config, Partial};
 use eyre::{bail, Result};
+use indexmap::IndexMap;
 use once_cell::sync::Lazy;
 use serde::ser::Error;
 use serde_derive::{Deserialize, Serialize};
@@ -29,6 +30,12 @@ pub struct Settings {
     pub always_keep_download: bool,
     #[config(env = "MISE_ALWAYS_KEEP_INSTALL", default = false)]
     pub always_keep_install: bool,
+    /// path to a local checkout of the aqua registry, if unset it is cloned into the cache
+    #[config(env = "MISE_AQUA_REGISTRY_DIR")]
+    pub aqua_registry_dir: Option<PathBuf>,
+    /// git url of the aqua registry used by the aqua backend
+    #[config(env = "MISE_AQUA_REGISTRY_URL", default = "https://github.com/aquaproj/aqua-registry")]
+    pub aqua_registry_url: String,
     #[cfg(asdf)]
     #[config(env = "MISE_ASDF", default = true)]
     pub asdf: bool,
@@ -40,17 +47,50 @@ pub struct Settings {
     /// also, the default behavior of `mise global` will be --pin
     #[config(env = "MISE_ASDF_COMPAT", default = false)]
     pub asdf_compat: bool,
+    /// set to true to skip checksum verification when downloading bun
+    #[config(env = "MISE_BUN_SKIP_CHECKSUM", default = false)]
+    pub bun_skip_checksum: bool,
+    /// path to a pem file with certificates to trust in addition to the system ones
+    #[config(env = "MISE_CA_BUNDLE")]
+    pub ca_bundle: Option<PathBuf>,
     /// use cargo-binstall instead of cargo install if available
     #[config(env = "MISE_CARGO_BINSTALL", default = true)]
     pub cargo_binstall: bool,
     #[config(env = "MISE_COLOR", default = true)]
     pub color: bool,
+    /// conda channel the conda backend installs packages from, a url or local directory
+    #[config(env = "MISE_CONDA_CHANNEL", default = "https://conda.anaconda.org/conda-forge")]
+    pub conda_channel: String,
+    /// set to true to skip checksum verification when downloading deno
+    #[config(env = "MISE_DENO_SKIP_CHECKSUM", default = false)]
+    pub deno_skip_checksum: bool,
     #[config(env = "MISE_DISABLE_DEFAULT_SHORTHANDS", default = false)]
     pub disable_default_shorthands: bool,
     #[config(env = "MISE_DISABLE_TOOLS", default = [], parse_env = list_by_comma)]
     pub disable_tools: BTreeSet<String>,
+    /// NuGet v3 service index or local folder feed used by the dotnet backend
+    #[config(env = "MISE_DOTNET_NUGET_SOURCE", default = "https://api.nuget.org/v3/index.json")]
+    pub dotnet_nuget_source: String,
+    /// keep downloaded archives in a cache shared by all installs
+    #[config(env = "MISE_DOWNLOAD_CACHE", default = true)]
+    pub download_cache: bool,
+    /// `mise cache prune` removes cached downloads not used in this long
+    #[config(env = "MISE_DOWNLOAD_CACHE_MAX_AGE", default = "30d")]
+    pub download_cache_max_age: String,
+    /// `mise cache prune` removes the least recently used downloads until the cache is this size
+    #[config(env = "MISE_DOWNLOAD_CACHE_MAX_SIZE", default = "10GiB")]
+    pub download_cache_max_size: String,
+    /// JSON file with end-of-life data keyed by tool name to use instead of eol_url
+    #[config(env = "MISE_EOL_FILE")]
+    pub eol_file: Option<PathBuf>,
+    /// endoflife.date compatible API that end-of-life data is fetched from
+    #[config(env = "MISE_EOL_URL", default = "https://endoflife.date/api")]
+    pub eol_url: String,
     #[config(env = "MISE_EXPERIMENTAL", default = false)]
     pub experimental: bool,
+    /// rubygems-compatible source used by the gem backend
+    #[config(env = "MISE_GEM_SOURCE", default = "https://rubygems.org")]
+    pub gem_source: String,
     /// after installing a go version, run `go install` on packages listed in this file
     #[config(env = "MISE_GO_DEFAULT_PACKAGES_FILE", default = "~/.default-go-packages")]
     pub go_default_packages_file: PathBuf,
@@ -79,8 +119,17 @@ pub struct Settings {
     /// set to true to skip checksum verification when downloading go sdk tarballs
     #[config(env = "MISE_GO_SKIP_CHECKSUM", default = false)]
     pub go_skip_checksum: bool,
+    /// proxy for http requests, HTTP_PROXY and friends are used when this isn't set
+    #[config(env = "MISE_HTTP_PROXY")]
+    pub http_proxy: Option<String>,
+    /// number of times to retry http requests that time out or fail with a 5xx status
+    #[config(env = "MISE_HTTP_RETRIES", default = 3)]
+    pub http_retries: u32,
     #[config(env = "MISE_HTTP_TIMEOUT", default = 30)]
     pub http_timeout: u64,
+    /// proxy for https requests, HTTPS_PROXY and friends are used when this isn't set
+    #[config(env = "MISE_HTTPS_PROXY")]
+    pub https_proxy: Option<String>,
     #[config(env = "MISE_JOBS", default = 4)]
     pub jobs: usize,
     #[config(env = "MISE_LEGACY_VERSION_FILE", default = true)]
@@ -89,10 +138,19 @@ pub struct Settings {
     pub legacy_version_file_disable_tools: BTreeSet<String>,
     #[config(env = "MISE_LIBGIT2", default = true)]
     pub libgit2: bool,
+    /// read and write a mise.lock next to each config file with the exact resolved versions
+    #[config(env = "MISE_LOCKFILE", default = false)]
+    pub lockfile: bool,
+    /// skip versions released more recently than this when resolving latest or prefix versions, e.g.: 7d
+    #[config(env = "MISE_MINIMUM_RELEASE_AGE")]
+    pub minimum_release_age: Option<String>,
     #[config(env = "MISE_NODE_COMPILE", default = false)]
     pub node_compile: bool,
     #[config(env = "MISE_NOT_FOUND_AUTO_INSTALL", default = true)]
     pub not_found_auto_install: bool,
+    /// do not access the network, use cached remote versions and installed tools
+    #[config(env = "MISE_OFFLINE", default = false)]
+    pub offline: bool,
     #[config(env = "MISE_PARANOID", default = false)]
     pub paranoid: bool,
     #[config(env = "MISE_PLUGIN_AUTOUPDATE_LAST_CHECK_DURATION", default = "7d")]
@@ -109,6 +167,9 @@ pub struct Settings {
     pub python_precompiled_arch: Option<String>,
     #[config(env = "MISE_PYTHON_PRECOMPILED_OS")]
     pub python_precompiled_os: Option<String>,
+    /// set to true to skip checksum verification when downloading precompiled python
+    #[config(env = "MISE_PYTHON_PRECOMPILED_SKIP_CHECKSUM", default = false)]
+    pub python_precompiled_skip_checksum: bool,
     #[config(env = "MISE_PYENV_REPO", default = "https://github.com/pyenv/pyenv.git")]
     pub python_pyenv_repo: String,
     #[config(env = "MISE_RAW", default = false)]
@@ -122,6 +183,12 @@ pub struct Settings {
     pub task_output: Option<String>,
     #[config(env = "MISE_TRUSTED_CONFIG_PATHS", default = [], parse_env = list_by_colon)]
     pub trusted_config_paths: BTreeSet<PathBuf>,
+    /// public keys to verify signatures of tool downloads with, by tool name
+    #[config(env = "MISE_TRUSTED_KEYS", parse_env = parse_json)]
+    pub trusted_keys: Option<IndexMap<String, Vec<String>>>,
+    /// rewrites urls before they are fetched, keys are url prefixes or regexes prefixed with "regex:"
+    #[config(env = "MISE_URL_REPLACEMENTS", parse_env = parse_json)]
+    pub url_replacements: Option<IndexMap<String, String>>,
     #[config(env = "MISE_QUIET", default = false)]
     pub quiet: bool,
     #[config(env = "MISE_VERBOSE", default = false)]
@@ -185,6 +252,10 @@ pub enum SettingsStatusMissingTools {
     Always,
 }
 
+fn parse_json<T: serde::de::DeserializeOwned>(s: &str) -> serde_json::Result<T> {
+    serde_json::from_str(s)
+}
+
 pub type SettingsPartial = <Settings as Config>::Partial;
 
 static SETTINGS: RwLock<Option<Arc<Settings>>> = RwLock::new(None);
@@ -297,6 +368,9 @@ impl Settings {
         if let Some(true) = m.get_one::<bool>("yes") {
             s.yes = Some(true);
         }
+        if let Some(true) = m.get_one::<bool>("offline") {
+            s.offline = Some(true);
+        }
         if let Some(true) = m.get_one::<bool>("quiet") {
             s.quiet = Some(true);
         }
@@ -405,6 +479,13 @@ impl Settings {
         Ok(())
     }
 
+    pub fn ensure_online(&self, what: &str) -> Result<()> {
+        if self.offline {
+            bail!("{what} requires network access but offline mode is enabled (--offline or MISE_OFFLINE)");
+        }
+        Ok(())
+    }
+
     pub fn trusted_config_paths(&self) -> impl Iterator<Item = PathBuf> + '_ {
         self.trusted_config_paths.iter().map(file::replace_path)
     }
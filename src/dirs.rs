@@ -19,3 +19,4 @@ pub static SHIMS: Lazy<&Path> = Lazy::new(|| &env::MISE_SHIMS_DIR);
 
 pub static TRACKED_CONFIGS: Lazy<PathBuf> = Lazy::new(|| STATE.join("tracked-configs"));
 pub static TRUSTED_CONFIGS: Lazy<PathBuf> = Lazy::new(|| STATE.join("trusted-configs"));
+pub static TOOL_USAGE: Lazy<PathBuf> = Lazy::new(|| STATE.join("tool-usage"));
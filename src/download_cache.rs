@@ -0,0 +1,245 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use eyre::{bail, eyre, Result};
+use filetime::{set_file_mtime, FileTime};
+use itertools::Itertools;
+
+use crate::config::Settings;
+use crate::file::display_path;
+use crate::hash::{file_hash_sha256, hash_to_str};
+use crate::http::HTTP;
+use crate::ui::progress_report::SingleReport;
+use crate::{dirs, file};
+
+/// downloads `url` to `path` through a cache shared by all installs
+///
+/// Files are stored once by sha256 in `~/.cache/mise/downloads/blobs` and looked up by url with
+/// the index in `~/.cache/mise/downloads/urls` so reinstalling a version, or installing it into
+/// another MISE_DATA_DIR, doesn't download it again. `verify` is called on the downloaded file
+/// before it is added to the cache and on cached files before they are reused, a cached file that
+/// fails it is evicted and downloaded again.
+pub fn download(
+    url: &str,
+    path: &Path,
+    pr: Option<&dyn SingleReport>,
+    verify: impl Fn(&Path) -> Result<()>,
+) -> Result<()> {
+    if !Settings::get().download_cache {
+        HTTP.download_file(url, path, pr)?;
+        return verify(path);
+    }
+    if let Some(blob) = lookup(url)? {
+        debug!("using cached download for {url}");
+        if let Some(pr) = pr {
+            pr.set_message(format!("using cached {}", display_path(&blob)));
+        }
+        file::create_dir_all(path.parent().unwrap())?;
+        link_or_copy(&blob, path)?;
+        match verify(path) {
+            Ok(()) => return Ok(()),
+            Err(err) => {
+                warn!("cached download of {url} failed verification: {err:#}");
+                evict(url, &blob)?;
+            }
+        }
+    }
+    HTTP.download_file(url, path, pr)?;
+    verify(path)?;
+    if let Err(err) = store(url, path) {
+        warn!("failed to cache download of {url}: {err:#}");
+    }
+    Ok(())
+}
+
+fn cache_dir() -> PathBuf {
+    dirs::CACHE.join("downloads")
+}
+
+fn index_path(url: &str) -> PathBuf {
+    cache_dir().join("urls").join(hash_to_str(&url))
+}
+
+fn blob_path(sha256: &str) -> PathBuf {
+    cache_dir().join("blobs").join(sha256)
+}
+
+fn lookup(url: &str) -> Result<Option<PathBuf>> {
+    let index = index_path(url);
+    if !index.exists() {
+        return Ok(None);
+    }
+    let sha256 = file::read_to_string(&index)?.trim().to_string();
+    let blob = blob_path(&sha256);
+    if !blob.exists() {
+        file::remove_file(&index)?;
+        return Ok(None);
+    }
+    if file_hash_sha256(&blob)? != sha256 {
+        warn!("cached download of {url} is corrupted, downloading it again");
+        file::remove_file(&blob)?;
+        file::remove_file(&index)?;
+        return Ok(None);
+    }
+    // the mtime is used as the last access time when pruning
+    set_file_mtime(&blob, FileTime::now())?;
+    Ok(Some(blob))
+}
+
+fn evict(url: &str, blob: &Path) -> Result<()> {
+    file::remove_file(blob)?;
+    file::remove_file(index_path(url))
+}
+
+fn store(url: &str, path: &Path) -> Result<()> {
+    let sha256 = file_hash_sha256(path)?;
+    let blob = blob_path(&sha256);
+    if !blob.exists() {
+        file::create_dir_all(blob.parent().unwrap())?;
+        let partial = blob.with_extension("part");
+        link_or_copy(path, &partial)?;
+        file::rename(&partial, &blob)?;
+    }
+    let index = index_path(url);
+    file::create_dir_all(index.parent().unwrap())?;
+    file::write(&index, &sha256)
+}
+
+/// hardlinks are used when the cache and the destination are on the same filesystem so cached
+/// archives don't take up space twice
+fn link_or_copy(from: &Path, to: &Path) -> Result<()> {
+    if to.exists() {
+        file::remove_file(to)?;
+    }
+    if std::fs::hard_link(from, to).is_err() {
+        file::copy(from, to)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct CachedDownload {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: SystemTime,
+}
+
+/// removes cached downloads that haven't been used in `max_age` and then the least recently
+/// used ones until the cache is smaller than `max_size`
+pub fn prune(
+    max_size: Option<u64>,
+    max_age: Option<Duration>,
+    dry_run: bool,
+) -> Result<Vec<CachedDownload>> {
+    let blobs_dir = cache_dir().join("blobs");
+    if !blobs_dir.exists() {
+        return Ok(vec![]);
+    }
+    let blobs = file::ls(&blobs_dir)?
+        .into_iter()
+        .map(|path| {
+            let metadata = path.metadata()?;
+            Ok(CachedDownload {
+                size: metadata.len(),
+                modified: metadata.modified()?,
+                path,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .sorted_by_key(|b| std::cmp::Reverse(b.modified));
+    let mut total = 0;
+    let mut removed = vec![];
+    for blob in blobs {
+        let age = blob.modified.elapsed().unwrap_or_default();
+        total += blob.size;
+        if max_age.is_some_and(|max_age| age > max_age) || max_size.is_some_and(|s| total > s) {
+            total -= blob.size;
+            if !dry_run {
+                file::remove_file(&blob.path)?;
+            }
+            removed.push(blob);
+        }
+    }
+    if !dry_run {
+        remove_dangling_urls()?;
+    }
+    Ok(removed)
+}
+
+fn remove_dangling_urls() -> Result<()> {
+    let urls_dir = cache_dir().join("urls");
+    if !urls_dir.exists() {
+        return Ok(());
+    }
+    for index in file::ls(&urls_dir)? {
+        let sha256 = file::read_to_string(&index)?;
+        if !blob_path(sha256.trim()).exists() {
+            file::remove_file(&index)?;
+        }
+    }
+    Ok(())
+}
+
+/// parses sizes like "500MB", "10GiB" or "1024"
+pub fn parse_size(s: &str) -> Result<u64> {
+    let s = s.trim();
+    let i = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (n, unit) = s.split_at(i);
+    let Ok(n) = n.parse::<u64>() else {
+        bail!("invalid size: {s}");
+    };
+    let multiplier = match unit.trim().to_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        "t" | "tb" | "tib" => 1 << 40,
+        _ => bail!("invalid size unit: {s}"),
+    };
+    n.checked_mul(multiplier)
+        .ok_or_else(|| eyre!("size too large: {s}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_size() {
+        assert_eq!(parse_size("1024").unwrap(), 1024);
+        assert_eq!(parse_size("500MB").unwrap(), 500 * 1024 * 1024);
+        assert_eq!(parse_size("10GiB").unwrap(), 10 * 1024 * 1024 * 1024);
+        assert!(parse_size("10 parsecs").is_err());
+        assert!(parse_size("GB").is_err());
+        assert!(parse_size("99999999999T").is_err());
+    }
+
+    #[test]
+    fn test_store_and_lookup() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = "https://example.com/test_store_and_lookup.tar.gz";
+        let download = dir.path().join("download.tar.gz");
+        file::write(&download, "archive").unwrap();
+        store(url, &download).unwrap();
+        let blob = lookup(url).unwrap().unwrap();
+        assert_eq!(file::read_to_string(&blob).unwrap(), "archive");
+
+        let reinstall = dir.path().join("reinstall.tar.gz");
+        super::download(url, &reinstall, None, |path| {
+            assert_eq!(file::read_to_string(path).unwrap(), "archive");
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(file::read_to_string(&reinstall).unwrap(), "archive");
+        assert!(lookup("https://example.com/missing.tar.gz")
+            .unwrap()
+            .is_none());
+
+        evict(url, &blob).unwrap();
+        assert!(lookup(url).unwrap().is_none());
+        assert!(!blob.exists());
+    }
+}
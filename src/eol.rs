@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use chrono::{NaiveDate, Utc};
+use eyre::Result;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::cache::CacheManager;
+use crate::config::Settings;
+use crate::duration::DAILY;
+use crate::http::HTTP_FETCH;
+use crate::toolset::ToolVersion;
+use crate::{dirs, file};
+
+/// a release line of a tool in an endoflife.date style dataset, e.g.:
+/// https://endoflife.date/api/nodejs.json
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EolCycle {
+    /// the version prefix of the release line, e.g.: "20" for node or "3.12" for python
+    pub cycle: String,
+    #[serde(default)]
+    pub release_date: Option<String>,
+    /// when active support ends, some tools only have security support after this
+    #[serde(default)]
+    pub support: Option<DateOrBool>,
+    pub eol: DateOrBool,
+    #[serde(default)]
+    pub latest: Option<String>,
+}
+
+/// endoflife.date uses `true`/`false` when the date is unknown or there is none
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum DateOrBool {
+    Bool(bool),
+    Date(String),
+}
+
+impl DateOrBool {
+    fn is_past(&self, today: NaiveDate) -> bool {
+        match self {
+            DateOrBool::Bool(b) => *b,
+            DateOrBool::Date(d) => {
+                NaiveDate::parse_from_str(d, "%Y-%m-%d").is_ok_and(|d| d <= today)
+            }
+        }
+    }
+}
+
+impl Display for DateOrBool {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateOrBool::Bool(true) => write!(f, "yes"),
+            DateOrBool::Bool(false) => write!(f, "no"),
+            DateOrBool::Date(d) => write!(f, "{d}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum EolStatus {
+    Supported,
+    /// past the end of active support but still getting security fixes
+    SecurityOnly,
+    Eol,
+}
+
+impl EolCycle {
+    pub fn status(&self) -> EolStatus {
+        let today = Utc::now().date_naive();
+        if self.eol.is_past(today) {
+            EolStatus::Eol
+        } else if self.support.as_ref().is_some_and(|s| s.is_past(today)) {
+            EolStatus::SecurityOnly
+        } else {
+            EolStatus::Supported
+        }
+    }
+
+    pub fn is_eol(&self) -> bool {
+        self.status() == EolStatus::Eol
+    }
+
+    fn matches(&self, version: &str) -> bool {
+        let version = version.trim_start_matches('v');
+        version == self.cycle || version.starts_with(&format!("{}.", self.cycle))
+    }
+}
+
+/// the endoflife.date product of tools that have one
+fn product(tool: &str) -> Option<&'static str> {
+    let product = match tool {
+        "elixir" => "elixir",
+        "erlang" => "erlang",
+        "go" => "go",
+        "kubectl" => "kubernetes",
+        "node" => "nodejs",
+        "php" => "php",
+        "python" => "python",
+        "ruby" => "ruby",
+        "terraform" => "terraform",
+        _ => return None,
+    };
+    Some(product)
+}
+
+/// the release line `tv` belongs to, None if there is no eol data for the tool or version
+pub fn find_cycle(tv: &ToolVersion) -> Result<Option<EolCycle>> {
+    let cycles = cycles(&tv.backend.short)?;
+    Ok(find_in(cycles, &tv.version))
+}
+
+fn find_in(cycles: Vec<EolCycle>, version: &str) -> Option<EolCycle> {
+    // the most specific cycle wins if a dataset has both "3" and "3.12"
+    cycles
+        .into_iter()
+        .filter(|c| c.matches(version))
+        .max_by_key(|c| c.cycle.len())
+}
+
+/// release lines of a tool from `eol_file` if set, otherwise from `eol_url`
+fn cycles(tool: &str) -> Result<Vec<EolCycle>> {
+    let settings = Settings::get();
+    if let Some(path) = &settings.eol_file {
+        let raw = file::read_to_string(file::replace_path(path))?;
+        let mut dataset: BTreeMap<String, Vec<EolCycle>> = serde_json::from_str(&raw)?;
+        return Ok(dataset.remove(tool).unwrap_or_default());
+    }
+    let product = match product(tool) {
+        Some(product) => product,
+        None => return Ok(vec![]),
+    };
+    let cache: CacheManager<Vec<EolCycle>> = CacheManager::new(
+        dirs::CACHE
+            .join("eol")
+            .join(format!("{product}-$KEY.msgpack.z")),
+    )
+    .with_fresh_duration(Some(DAILY));
+    let cycles = cache.get_or_try_init(|| {
+        let url = format!("{}/{product}.json", settings.eol_url.trim_end_matches('/'));
+        HTTP_FETCH.json(url)
+    })?;
+    Ok(cycles.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_find_in() {
+        let cycles: Vec<EolCycle> = serde_json::from_str(
+            r#"[
+                {"cycle": "3.12", "releaseDate": "2023-10-02", "support": "2025-04-02", "eol": "2028-10-02", "latest": "3.12.4"},
+                {"cycle": "3.1", "releaseDate": "2012-04-09", "support": true, "eol": "2012-04-09"},
+                {"cycle": "3", "eol": false}
+            ]"#,
+        )
+        .unwrap();
+        let cycle = find_in(cycles.clone(), "3.12.1").unwrap();
+        assert_eq!(cycle.cycle, "3.12");
+        assert_eq!(cycle.latest.as_deref(), Some("3.12.4"));
+        let cycle = find_in(cycles.clone(), "3.1.5").unwrap();
+        assert_eq!(cycle.cycle, "3.1");
+        assert_eq!(cycle.status(), EolStatus::Eol);
+        assert_eq!(cycle.support, Some(DateOrBool::Bool(true)));
+        let cycle = find_in(cycles.clone(), "3.13.0").unwrap();
+        assert_eq!(cycle.cycle, "3");
+        assert_eq!(cycle.status(), EolStatus::Supported);
+        assert_eq!(find_in(cycles, "2.7.18"), None);
+    }
+}
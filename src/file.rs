@@ -10,7 +10,7 @@ use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::Duration;
 
-use color_eyre::eyre::{Context, Result};
+use color_eyre::eyre::{eyre, Context, Result};
 use filetime::{set_file_times, FileTime};
 use flate2::read::GzDecoder;
 use itertools::Itertools;
@@ -470,6 +470,29 @@ pub fn unzip(archive: &Path, dest: &Path) -> Result<()> {
         .wrap_err_with(|| format!("failed to extract zip archive: {}", display_path(archive)))
 }
 
+pub fn untar_xy(archive: &Path, dest: &Path) -> Result<()> {
+    let archive = archive
+        .to_str()
+        .ok_or(eyre!("Failed to read archive path"))?;
+    let dest = dest
+        .to_str()
+        .ok_or(eyre!("Failed to read destination path"))?;
+
+    let output = std::process::Command::new("tar")
+        .arg("-xf")
+        .arg(archive)
+        .arg("-C")
+        .arg(dest)
+        .output()?;
+
+    if !output.status.success() {
+        let err = String::from_utf8_lossy(&output.stderr);
+        return Err(eyre!("Failed to extract tar: {}", err));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::Deref;
@@ -8,7 +8,7 @@ use xx::file;
 
 use crate::cmd;
 use crate::config::Settings;
-use crate::file::touch_dir;
+use crate::file::{display_path, touch_dir};
 
 pub struct Git {
     pub dir: PathBuf,
@@ -61,6 +61,7 @@ impl Git {
     }
 
     pub fn update(&self, gitref: Option<String>) -> Result<(String, String)> {
+        Settings::get().ensure_online(&format!("updating {}", display_path(&self.dir)))?;
         let gitref = gitref.map_or_else(|| self.current_branch(), Ok)?;
         debug!("updating {} to {}", self.dir.display(), gitref);
         let exec = |cmd: Expression| match cmd.stderr_to_stdout().stdout_capture().unchecked().run()
@@ -103,6 +104,7 @@ impl Git {
     }
 
     pub fn clone(&self, url: &str) -> Result<()> {
+        Settings::get().ensure_online(&format!("cloning {url}"))?;
         debug!("cloning {} to {}", url, self.dir.display());
         if let Some(parent) = self.dir.parent() {
             file::mkdirp(parent)?;
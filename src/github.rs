@@ -1,16 +1,66 @@
+use std::path::Path;
+
 use serde_derive::Deserialize;
 
+use crate::hash;
+use crate::http::HTTP;
+
 #[derive(Debug, Deserialize)]
 pub struct GithubRelease {
     pub tag_name: String,
     // pub name: Option<String>,
     // pub body: Option<String>,
-    // pub prerelease: bool,
+    #[serde(default)]
+    pub prerelease: bool,
     // pub created_at: String,
-    // pub published_at: Option<String>,
+    pub published_at: Option<String>,
+    #[serde(default)]
+    pub assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GithubAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl GithubRelease {
+    /// the bundle `cosign sign-blob --bundle` created for the asset `name`, if it was published
+    pub fn cosign_bundle(&self, name: &str) -> Option<&GithubAsset> {
+        let bundles = [format!("{name}.bundle"), format!("{name}.sigstore.json")];
+        self.assets.iter().find(|a| bundles.contains(&a.name))
+    }
+}
+
+/// verifies `path`, downloaded from the asset `name` of `release`, with its cosign bundle
+pub fn verify_cosign_bundle(
+    tool: &str,
+    release: &GithubRelease,
+    name: &str,
+    path: &Path,
+) -> eyre::Result<()> {
+    let bundle_path = path.with_file_name(format!("{name}.bundle"));
+    let sig = match release.cosign_bundle(name) {
+        Some(bundle) if hash::has_trusted_keys(tool) => {
+            HTTP.download_file(&bundle.browser_download_url, &bundle_path, None)?;
+            Some(hash::Signature::Cosign(&bundle_path))
+        }
+        _ => None,
+    };
+    hash::verify_signature(tool, path, sig)
 }
 
 pub fn list_releases(repo: &str) -> eyre::Result<Vec<GithubRelease>> {
     let url = format!("https://api.github.com/repos/{}/releases", repo);
     crate::http::HTTP_FETCH.json(url)
 }
+
+pub fn get_release(repo: &str, tag: &str) -> eyre::Result<GithubRelease> {
+    let url = format!("https://api.github.com/repos/{repo}/releases/tags/{tag}");
+    crate::http::HTTP_FETCH.json(url)
+}
+
+pub fn get_latest_release(repo: &str) -> eyre::Result<GithubRelease> {
+    let url = format!("https://api.github.com/repos/{repo}/releases/latest");
+    crate::http::HTTP_FETCH.json(url)
+}
@@ -4,11 +4,14 @@ use std::hash::{Hash, Hasher};
 use std::io::{Read, Write};
 use std::path::Path;
 
-use eyre::{ensure, Result};
+use duct::Expression;
+use eyre::{bail, ensure, Result, WrapErr};
+use itertools::Itertools;
 use rayon::prelude::*;
 use sha2::{Digest, Sha256};
 use siphasher::sip::SipHasher;
 
+use crate::config::Settings;
 use crate::file::display_path;
 use crate::ui::progress_report::SingleReport;
 
@@ -69,6 +72,190 @@ pub fn parse_shasums(text: &str) -> HashMap<String, String> {
         .collect()
 }
 
+/// finds the checksum for a file in a checksum file which is either just the checksum or
+/// lines of "<checksum>  <filename>"
+pub fn parse_checksum(text: &str, filename: &str) -> Option<String> {
+    let lines = text
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .collect::<Vec<_>>();
+    let checksum = match lines.as_slice() {
+        [line] if line.split_whitespace().count() == 1 => Some(line.trim()),
+        _ => lines.iter().find_map(|l| {
+            let mut parts = l.split_whitespace();
+            let checksum = parts.next()?;
+            let name = parts.next()?.trim_start_matches(['*', '.', '/']);
+            (name == filename).then_some(checksum)
+        }),
+    };
+    checksum.map(|c| c.to_lowercase())
+}
+
+/// a detached signature of a downloaded file
+#[derive(Debug, Clone, Copy)]
+pub enum Signature<'a> {
+    /// gpg signature, e.g.: node's SHASUMS256.txt.sig
+    Gpg(&'a Path),
+    /// minisign signature, e.g.: zig's .minisig files
+    Minisign(&'a Path),
+    /// bundle created with `cosign sign-blob --bundle`
+    Cosign(&'a Path),
+}
+
+impl Signature<'_> {
+    fn kind(&self) -> &'static str {
+        match self {
+            Signature::Gpg(_) => "gpg",
+            Signature::Minisign(_) => "minisign",
+            Signature::Cosign(_) => "cosign",
+        }
+    }
+}
+
+/// verifies that `path` was signed by one of the keys pinned for `tool` in the `trusted_keys`
+/// setting. `sig` is None if the signature couldn't be found. Without pinned keys, a signature or
+/// keys of the signature's type this does nothing unless `paranoid` is set in which case it fails.
+pub fn verify_signature(tool: &str, path: &Path, sig: Option<Signature>) -> Result<()> {
+    let settings = Settings::get();
+    if !has_trusted_keys(tool) {
+        ensure!(
+            !settings.paranoid,
+            "no keys for {tool} in trusted_keys to verify the signature of {} with (paranoid mode)",
+            display_path(path)
+        );
+        debug!("no keys for {tool} in trusted_keys");
+        return Ok(());
+    }
+    let Some(sig) = sig else {
+        ensure!(
+            !settings.paranoid,
+            "no signature found for {} (paranoid mode)",
+            display_path(path)
+        );
+        debug!("no signature found for {}", display_path(path));
+        return Ok(());
+    };
+    let keys = trusted_keys(&settings, tool, sig.kind());
+    if keys.is_empty() {
+        ensure!(
+            !settings.paranoid,
+            "no {} keys for {tool} in trusted_keys to verify {} with (paranoid mode)",
+            sig.kind(),
+            display_path(path)
+        );
+        debug!("no {} keys for {tool} in trusted_keys", sig.kind());
+        return Ok(());
+    }
+    debug!(
+        "verifying {} signature of {}",
+        sig.kind(),
+        display_path(path)
+    );
+    match sig {
+        Signature::Gpg(sig) => verify_gpg(path, sig, &keys),
+        Signature::Minisign(sig) => verify_with_any_key(&keys, |key| {
+            cmd!("minisign", "-V", "-q", "-P", key, "-m", path, "-x", sig)
+        }),
+        Signature::Cosign(bundle) => verify_with_any_key(&keys, |key| {
+            cmd!(
+                "cosign",
+                "verify-blob",
+                "--key",
+                key,
+                "--bundle",
+                bundle,
+                path
+            )
+        }),
+    }
+    .wrap_err_with(|| format!("failed to verify signature of {}", display_path(path)))
+}
+
+/// true if `trusted_keys` pins any keys for `tool`, signatures don't need to be fetched otherwise
+pub fn has_trusted_keys(tool: &str) -> bool {
+    Settings::get()
+        .trusted_keys
+        .as_ref()
+        .and_then(|keys| keys.get(tool))
+        .is_some_and(|keys| !keys.is_empty())
+}
+
+/// keys are prefixed with their type, e.g.: "gpg:<fingerprint>", "minisign:<public key>" or
+/// "cosign:<path to public key>"
+fn trusted_keys(settings: &Settings, tool: &str, kind: &str) -> Vec<String> {
+    let Some(keys) = settings
+        .trusted_keys
+        .as_ref()
+        .and_then(|keys| keys.get(tool))
+    else {
+        return vec![];
+    };
+    keys.iter()
+        .filter_map(|key| key.strip_prefix(kind)?.strip_prefix(':'))
+        .map(|key| key.trim().to_string())
+        .collect()
+}
+
+fn verify_gpg(path: &Path, sig: &Path, fingerprints: &[String]) -> Result<()> {
+    let output = cmd!("gpg", "--batch", "--status-fd", "1", "--verify", sig, path)
+        .stdout_capture()
+        .stderr_capture()
+        .unchecked()
+        .run()?;
+    let status = String::from_utf8_lossy(&output.stdout);
+    ensure!(
+        output.status.success(),
+        "gpg --verify failed: {}",
+        String::from_utf8_lossy(&output.stderr).trim()
+    );
+    let signers = gpg_signers(&status);
+    let normalize = |fpr: &str| fpr.replace(' ', "").to_uppercase();
+    ensure!(
+        signers
+            .iter()
+            .any(|s| fingerprints.iter().any(|f| normalize(f) == *s)),
+        "signed by {} which is not in trusted_keys",
+        signers.join(", ")
+    );
+    Ok(())
+}
+
+/// fingerprints of the signing key and its primary key from the VALIDSIG lines of `gpg --status-fd`
+fn gpg_signers(status: &str) -> Vec<String> {
+    status
+        .lines()
+        .filter_map(|l| l.strip_prefix("[GNUPG:] VALIDSIG "))
+        .flat_map(|l| {
+            let fields = l.split_whitespace().collect::<Vec<_>>();
+            [fields.first(), fields.get(9)]
+                .into_iter()
+                .flatten()
+                .map(|f| f.to_uppercase())
+                .collect::<Vec<_>>()
+        })
+        .unique()
+        .collect()
+}
+
+fn verify_with_any_key(keys: &[String], cmd: impl Fn(&str) -> Expression) -> Result<()> {
+    let mut errors = vec![];
+    for key in keys {
+        let output = cmd(key)
+            .stdout_capture()
+            .stderr_capture()
+            .unchecked()
+            .run()?;
+        if output.status.success() {
+            return Ok(());
+        }
+        errors.push(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+    bail!(
+        "not signed by any key in trusted_keys: {}",
+        errors.join(", ")
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use insta::assert_snapshot;
@@ -91,4 +278,44 @@ mod tests {
         let hash = file_hash_sha256(path).unwrap();
         assert_snapshot!(hash);
     }
+
+    #[test]
+    fn test_parse_checksum() {
+        assert_eq!(
+            parse_checksum("ABC123\n", "foo.tar.gz"),
+            Some("abc123".into())
+        );
+        let text = "abc  foo.tar.gz\ndef *bar.tar.gz\n";
+        assert_eq!(parse_checksum(text, "bar.tar.gz"), Some("def".into()));
+        assert_eq!(parse_checksum(text, "baz.tar.gz"), None);
+    }
+
+    #[test]
+    fn test_gpg_signers() {
+        let status = "[GNUPG:] NEWSIG\n[GNUPG:] GOODSIG 1C050899334244A8 Node.js Release\n[GNUPG:] VALIDSIG 8FCCA13FEF1D0C2E91008E09770F7A9A5AE15600 2024-01-01 1704067200 0 4 0 1 10 00 8FCCA13FEF1D0C2E91008E09770F7A9A5AE15600\n";
+        assert_eq!(
+            gpg_signers(status),
+            vec!["8FCCA13FEF1D0C2E91008E09770F7A9A5AE15600"]
+        );
+        assert!(gpg_signers("[GNUPG:] BADSIG 1C050899334244A8\n").is_empty());
+    }
+
+    #[test]
+    fn test_verify_signature_without_trusted_keys() {
+        reset();
+        Settings::reset(Some(crate::config::settings::SettingsPartial {
+            paranoid: Some(true),
+            ..Default::default()
+        }));
+        let path = Path::new(".test-tool-versions");
+        let sig = Path::new("missing.sig");
+        let err = verify_signature("tiny", path, Some(Signature::Gpg(sig))).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "no keys for tiny in trusted_keys to verify the signature of .test-tool-versions with (paranoid mode)"
+        );
+        Settings::reset(None);
+        assert!(verify_signature("tiny", path, None).is_ok());
+        assert!(verify_signature("tiny", path, Some(Signature::Gpg(sig))).is_ok());
+    }
 }
@@ -1,11 +1,14 @@
-use std::fs::File;
+use std::fs::{File, OpenOptions};
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
-use eyre::{bail, Report, Result};
+use eyre::{bail, Report, Result, WrapErr};
+use indexmap::IndexMap;
 use once_cell::sync::Lazy;
-use reqwest::{ClientBuilder, IntoUrl, Response};
+use regex::Regex;
+use reqwest::header::{ACCEPT_ENCODING, ETAG, IF_RANGE, LAST_MODIFIED, RANGE};
+use reqwest::{Certificate, ClientBuilder, IntoUrl, NoProxy, Proxy, Response, StatusCode};
 use tokio::runtime::Runtime;
 use url::Url;
 
@@ -34,34 +37,88 @@ pub struct Client {
 impl Client {
     fn new(timeout: Duration) -> Result<Self> {
         Ok(Self {
-            reqwest: Self::_new()
+            reqwest: Self::_new()?
                 .read_timeout(timeout)
                 .connect_timeout(timeout)
                 .build()?,
         })
     }
 
-    fn _new() -> ClientBuilder {
-        ClientBuilder::new()
+    fn _new() -> Result<ClientBuilder> {
+        let mut builder = ClientBuilder::new()
             .user_agent(format!("mise/{}", &*version::VERSION))
-            .gzip(true)
+            .gzip(true);
+        for proxy in proxies()? {
+            builder = builder.proxy(proxy);
+        }
+        if let Some(ca_bundle) = &Settings::get().ca_bundle {
+            for cert in load_ca_bundle(ca_bundle)? {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+        Ok(builder)
     }
 
     async fn get<U: IntoUrl>(&self, url: U) -> Result<Response> {
+        self.get_from(url, 0, None).await
+    }
+
+    /// requests the bytes of `url` starting at `offset` with a range request, servers that don't
+    /// support ranges, or whose file no longer matches `if_range`, respond with the whole body and
+    /// a 200 status
+    async fn get_from<U: IntoUrl>(
+        &self,
+        url: U,
+        offset: u64,
+        if_range: Option<&str>,
+    ) -> Result<Response> {
+        let url = replace_url(url.into_url()?)?;
+        Settings::get().ensure_online(&format!("GET {url}"))?;
         let get = |url: Url| async move {
-            debug!("GET {}", &url);
-            let mut req = self.reqwest.get(url.clone());
-            if url.host_str() == Some("api.github.com") {
-                if let Some(token) = &*env::GITHUB_API_TOKEN {
-                    req = req.header("authorization", format!("token {}", token));
+            let retries = Settings::get().http_retries;
+            let mut attempt = 0;
+            loop {
+                debug!("GET {}", &url);
+                let mut req = self.reqwest.get(url.clone());
+                if url.host_str() == Some("api.github.com") {
+                    if let Some(token) = env::GITHUB_API_TOKEN
+                        .as_ref()
+                        .or(env::GITHUB_TOKEN.as_ref())
+                    {
+                        req = req.header("authorization", format!("token {}", token));
+                    }
                 }
+                if offset > 0 {
+                    // the offset is in bytes of the file, not of a compressed response
+                    req = req
+                        .header(RANGE, format!("bytes={offset}-"))
+                        .header(ACCEPT_ENCODING, "identity");
+                    if let Some(if_range) = if_range {
+                        req = req.header(IF_RANGE, if_range);
+                    }
+                }
+                let err = match req.send().await {
+                    Ok(resp) if attempt < retries && resp.status().is_server_error() => {
+                        resp.status().to_string()
+                    }
+                    Ok(resp) => {
+                        debug!("GET {url} {}", resp.status());
+                        resp.error_for_status_ref()?;
+                        return Ok(resp);
+                    }
+                    Err(err) if attempt < retries && err.is_timeout() => err.to_string(),
+                    Err(err) => return Err(Report::from(err)),
+                };
+                attempt += 1;
+                let delay = backoff(attempt);
+                warn!(
+                    "GET {url} failed: {err}, retrying in {}s ({attempt}/{retries})",
+                    delay.as_secs()
+                );
+                tokio::time::sleep(delay).await;
             }
-            let resp = req.send().await?;
-            debug!("GET {url} {}", resp.status());
-            resp.error_for_status_ref()?;
-            Ok(resp)
         };
-        let mut url = url.into_url().unwrap();
+        let mut url = url;
         let resp = match get(url.clone()).await {
             Ok(resp) => resp,
             Err(_) if url.scheme() == "http" => {
@@ -107,6 +164,10 @@ impl Client {
         Ok(json)
     }
 
+    /// downloads to a ".part" file next to `path` which is renamed once it is complete. If the
+    /// connection drops the download is resumed from the end of the ".part" file as long as the
+    /// server still has the same file. A ".part" file that already exists is resumed as well but
+    /// tool installs clear their download dir first so across runs they start over.
     pub fn download_file<U: IntoUrl>(
         &self,
         url: U,
@@ -115,26 +176,97 @@ impl Client {
     ) -> Result<()> {
         let url = url.into_url()?;
         debug!("GET Downloading {} to {}", &url, display_path(path));
-
+        file::create_dir_all(path.parent().unwrap())?;
+        let part = part_path(path);
+        let retries = Settings::get().http_retries;
         let rt = self.runtime()?;
-        rt.block_on(async {
-            let mut resp = self.get(url).await?;
-            if let Some(length) = resp.content_length() {
-                if let Some(pr) = pr {
-                    pr.set_length(length);
+        let mut attempt = 0;
+        loop {
+            match rt.block_on(self.download_part(url.clone(), &part, pr)) {
+                Ok(()) => break,
+                Err(err) if attempt < retries && is_interrupted(&err) => {
+                    attempt += 1;
+                    let delay = backoff(attempt);
+                    warn!(
+                        "download of {url} was interrupted: {err}, resuming in {}s ({attempt}/{retries})",
+                        delay.as_secs()
+                    );
+                    std::thread::sleep(delay);
                 }
+                Err(err) => return Err(err),
             }
+        }
+        file::rename(&part, path)?;
+        let validator = validator_path(&part);
+        if validator.exists() {
+            file::remove_file(validator)?;
+        }
+        Ok(())
+    }
 
-            file::create_dir_all(path.parent().unwrap())?;
-            let mut file = File::create(path)?;
-            while let Some(chunk) = resp.chunk().await? {
-                file.write_all(&chunk)?;
-                if let Some(pr) = pr {
-                    pr.inc(chunk.len() as u64);
-                }
+    async fn download_part(
+        &self,
+        url: Url,
+        part: &Path,
+        pr: Option<&dyn SingleReport>,
+    ) -> Result<()> {
+        let validator_path = validator_path(part);
+        let mut offset = part.metadata().map(|m| m.len()).unwrap_or_default();
+        let validator = match offset {
+            0 => None,
+            _ => file::read_to_string(&validator_path).ok(),
+        };
+        if offset > 0 && validator.is_none() {
+            debug!(
+                "can't tell if {} is from {url}, starting over",
+                display_path(part)
+            );
+            offset = 0;
+        }
+        let mut resp = match self
+            .get_from(url.clone(), offset, validator.as_deref())
+            .await
+        {
+            Err(err) if offset > 0 && error_code(&err) == Some(416) => {
+                // the .part file is longer than the file so it is from something else, start over
+                self.get_from(url.clone(), 0, None).await?
             }
-            Ok::<(), eyre::Error>(())
-        })?;
+            resp => resp?,
+        };
+        let offset = match resp.status() {
+            StatusCode::PARTIAL_CONTENT if response_validator(&resp) == validator => {
+                debug!("resuming download of {url} at {offset} bytes");
+                offset
+            }
+            StatusCode::PARTIAL_CONTENT => {
+                // the server ignored If-Range and the file changed since the .part file was written
+                debug!("{url} changed since it was partially downloaded, starting over");
+                resp = self.get_from(url.clone(), 0, None).await?;
+                0
+            }
+            _ => 0,
+        };
+        if offset == 0 {
+            match response_validator(&resp) {
+                Some(validator) => file::write(&validator_path, validator)?,
+                None if validator_path.exists() => file::remove_file(&validator_path)?,
+                None => {}
+            }
+        }
+        if let (Some(pr), Some(length)) = (pr, resp.content_length()) {
+            pr.set_length(offset + length);
+            pr.set_position(offset);
+        }
+        let mut file = match offset {
+            0 => File::create(part)?,
+            _ => OpenOptions::new().append(true).open(part)?,
+        };
+        while let Some(chunk) = resp.chunk().await? {
+            file.write_all(&chunk)?;
+            if let Some(pr) = pr {
+                pr.inc(chunk.len() as u64);
+            }
+        }
         Ok(())
     }
 
@@ -158,3 +290,190 @@ pub fn error_code(e: &Report) -> Option<u16> {
         None
     }
 }
+
+fn part_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".part");
+    path.with_file_name(name)
+}
+
+/// where the validator of the file being downloaded to `part` is kept so it can be resumed
+fn validator_path(part: &Path) -> PathBuf {
+    let mut name = part.file_name().unwrap_or_default().to_os_string();
+    name.push(".validator");
+    part.with_file_name(name)
+}
+
+/// the strong ETag, or else the Last-Modified date, of a response which are what If-Range accepts
+fn response_validator(resp: &Response) -> Option<String> {
+    let header = |name| resp.headers().get(name).and_then(|v| v.to_str().ok());
+    header(ETAG)
+        .filter(|etag| !etag.starts_with("W/"))
+        .or_else(|| header(LAST_MODIFIED))
+        .map(String::from)
+}
+
+/// errors that happened while reading the body of a response so the download can be resumed
+fn is_interrupted(err: &Report) -> bool {
+    err.downcast_ref::<reqwest::Error>()
+        .is_some_and(|err| err.is_body() || err.is_decode())
+}
+
+/// delay before retrying a request for the nth time: 1s, 2s, 4s, ... up to 30s
+fn backoff(attempt: u32) -> Duration {
+    Duration::from_secs(2u64.saturating_pow(attempt.saturating_sub(1)).min(30))
+}
+
+/// proxies from the http_proxy/https_proxy settings, hosts in NO_PROXY are fetched directly
+///
+/// Adding a proxy turns off reqwest's own HTTP_PROXY/HTTPS_PROXY/ALL_PROXY handling so this is
+/// empty unless one of the settings is set.
+fn proxies() -> Result<Vec<Proxy>> {
+    let settings = Settings::get();
+    let mut proxies = vec![];
+    if let Some(proxy) = &settings.https_proxy {
+        debug!("using https proxy {proxy}");
+        proxies.push(Proxy::https(proxy)?.no_proxy(NoProxy::from_env()));
+    }
+    if let Some(proxy) = &settings.http_proxy {
+        debug!("using http proxy {proxy}");
+        proxies.push(Proxy::http(proxy)?.no_proxy(NoProxy::from_env()));
+    }
+    Ok(proxies)
+}
+
+/// certificates from the `ca_bundle` setting, trusted in addition to the system ones
+fn load_ca_bundle(path: &Path) -> Result<Vec<Certificate>> {
+    let pem = std::fs::read(path)
+        .wrap_err_with(|| format!("failed to read ca_bundle {}", display_path(path)))?;
+    Certificate::from_pem_bundle(&pem)
+        .wrap_err_with(|| format!("failed to parse ca_bundle {}", display_path(path)))
+}
+
+/// rewrites `url` with the first matching rule in the `url_replacements` setting so downloads can
+/// go through internal mirrors
+pub fn replace_url(url: Url) -> Result<Url> {
+    let settings = Settings::get();
+    let Some(replacements) = &settings.url_replacements else {
+        return Ok(url);
+    };
+    match apply_url_replacements(url.as_str(), replacements)? {
+        Some(replaced) => {
+            debug!("replacing {url} with {replaced}");
+            Ok(Url::parse(&replaced)?)
+        }
+        None => Ok(url),
+    }
+}
+
+/// keys are either url prefixes, e.g.: "https://nodejs.org/dist/", or regexes prefixed with
+/// "regex:" whose replacement can reference capture groups with "$1"
+fn apply_url_replacements(
+    url: &str,
+    replacements: &IndexMap<String, String>,
+) -> Result<Option<String>> {
+    for (from, to) in replacements {
+        if let Some(pattern) = from.strip_prefix("regex:") {
+            let re = Regex::new(pattern)
+                .wrap_err_with(|| format!("invalid regex in url_replacements: {pattern}"))?;
+            if re.is_match(url) {
+                return Ok(Some(re.replace(url, to.as_str()).to_string()));
+            }
+        } else if let Some(rest) = url.strip_prefix(from.as_str()) {
+            return Ok(Some(format!("{to}{rest}")));
+        }
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::test::reset;
+
+    use super::*;
+
+    #[test]
+    fn test_part_path() {
+        assert_eq!(
+            part_path(Path::new("/tmp/node-v20.0.0.tar.gz")),
+            PathBuf::from("/tmp/node-v20.0.0.tar.gz.part")
+        );
+        assert_eq!(
+            validator_path(Path::new("/tmp/node-v20.0.0.tar.gz.part")),
+            PathBuf::from("/tmp/node-v20.0.0.tar.gz.part.validator")
+        );
+    }
+
+    #[test]
+    fn test_download_file_resume() {
+        reset();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/file.txt", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut req = vec![];
+            let mut buf = [0; 1024];
+            while !String::from_utf8_lossy(&req).contains("\r\n\r\n") {
+                let n = stream.read(&mut buf).unwrap();
+                req.extend_from_slice(&buf[..n]);
+            }
+            stream
+                .write_all(b"HTTP/1.1 206 Partial Content\r\nETag: \"v1\"\r\nContent-Range: bytes 6-10/11\r\nContent-Length: 5\r\nConnection: close\r\n\r\nworld")
+                .unwrap();
+            String::from_utf8_lossy(&req).to_lowercase()
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.txt");
+        let part = part_path(&path);
+        file::write(&part, "hello ").unwrap();
+        file::write(validator_path(&part), "\"v1\"").unwrap();
+
+        HTTP.download_file(&url, &path, None).unwrap();
+        let req = server.join().unwrap();
+        assert!(req.contains("range: bytes=6-"));
+        assert!(req.contains("if-range: \"v1\""));
+        assert_eq!(file::read_to_string(&path).unwrap(), "hello world");
+        assert!(!part.exists());
+        assert!(!validator_path(&part).exists());
+    }
+
+    #[test]
+    fn test_backoff() {
+        assert_eq!(backoff(1), Duration::from_secs(1));
+        assert_eq!(backoff(2), Duration::from_secs(2));
+        assert_eq!(backoff(3), Duration::from_secs(4));
+        assert_eq!(backoff(10), Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_apply_url_replacements() {
+        let replacements = IndexMap::from([
+            (
+                "https://nodejs.org/dist/".to_string(),
+                "https://mirror.example.com/node/".to_string(),
+            ),
+            (
+                r"regex:^https://github\.com/([^/]+)/([^/]+)/".to_string(),
+                "https://gh.example.com/$1-$2/".to_string(),
+            ),
+        ]);
+        let replace = |url: &str| apply_url_replacements(url, &replacements).unwrap();
+        assert_eq!(
+            replace("https://nodejs.org/dist/v20.0.0/node-v20.0.0-linux-x64.tar.gz"),
+            Some("https://mirror.example.com/node/v20.0.0/node-v20.0.0-linux-x64.tar.gz".into())
+        );
+        assert_eq!(
+            replace("https://github.com/jdx/mise/releases/download/v1.0.0/mise.tar.gz"),
+            Some("https://gh.example.com/jdx-mise/releases/download/v1.0.0/mise.tar.gz".into())
+        );
+        assert_eq!(replace("https://go.dev/dl/"), None);
+
+        let invalid = IndexMap::from([("regex:(".to_string(), "".to_string())]);
+        assert!(apply_url_replacements("https://go.dev/dl/", &invalid).is_err());
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use chrono::{SecondsFormat, Utc};
+use eyre::Result;
+use rayon::prelude::*;
+use serde_derive::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::file::display_path;
+use crate::hash::file_hash_sha256;
+use crate::toolset::ToolVersion;
+use crate::{file, lockfile};
+
+/// what went into an install directory, written next to the backend meta file after every install
+/// so `mise verify` can tell if files were changed afterwards
+///
+/// ```json
+/// {
+///   "tool": "node",
+///   "version": "20.11.1",
+///   "installed_at": "2024-02-14T12:00:00Z",
+///   "url": "https://nodejs.org/dist/v20.11.1/node-v20.11.1-linux-x64.tar.gz",
+///   "sha256": "...",
+///   "files": {
+///     "bin/node": "sha256:...",
+///     "bin/npm": "symlink:../lib/node_modules/npm/bin/npm-cli.js"
+///   }
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InstallManifest {
+    pub tool: String,
+    pub version: String,
+    pub installed_at: String,
+    /// the archive the version was installed from if the backend downloaded one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+    /// paths relative to the install directory
+    pub files: BTreeMap<String, String>,
+}
+
+/// differences between an install directory and its manifest
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Verification {
+    pub modified: Vec<String>,
+    pub missing: Vec<String>,
+    pub extra: Vec<String>,
+}
+
+impl Verification {
+    pub fn is_ok(&self) -> bool {
+        self.modified.is_empty() && self.missing.is_empty() && self.extra.is_empty()
+    }
+}
+
+impl InstallManifest {
+    /// hashes every file in the install directory of `tv`
+    pub fn create(tv: &ToolVersion) -> Result<Self> {
+        let download = lockfile::get_download(tv);
+        Ok(Self {
+            tool: tv.backend.full.clone(),
+            version: tv.version.clone(),
+            installed_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            url: download.as_ref().and_then(|d| d.url.clone()),
+            sha256: download.and_then(|d| d.sha256),
+            files: hash_dir(&tv.install_path())?,
+        })
+    }
+
+    pub fn read(tv: &ToolVersion) -> Result<Option<Self>> {
+        let path = manifest_path(tv);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let manifest = serde_json::from_str(&file::read_to_string(&path)?)?;
+        Ok(Some(manifest))
+    }
+
+    pub fn write(&self, tv: &ToolVersion) -> Result<()> {
+        let path = manifest_path(tv);
+        debug!("writing install manifest {}", display_path(&path));
+        file::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// compares the files currently in the install directory of `tv` with the manifest
+    pub fn verify(&self, tv: &ToolVersion) -> Result<Verification> {
+        let current = hash_dir(&tv.install_path())?;
+        Ok(compare(&self.files, &current))
+    }
+}
+
+pub fn remove(tv: &ToolVersion) -> Result<()> {
+    let path = manifest_path(tv);
+    if path.exists() {
+        file::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// e.g.: ~/.local/share/mise/installs/node/.mise.manifest.20.11.1.json
+fn manifest_path(tv: &ToolVersion) -> PathBuf {
+    let install_path = tv.install_path();
+    let pathname = install_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    tv.backend
+        .installs_path
+        .join(format!(".mise.manifest.{pathname}.json"))
+}
+
+fn hash_dir(dir: &Path) -> Result<BTreeMap<String, String>> {
+    let entries = WalkDir::new(dir)
+        .follow_links(false)
+        .into_iter()
+        // python writes bytecode caches next to modules the first time they're imported
+        .filter_entry(|e| e.file_name() != "__pycache__")
+        .collect::<walkdir::Result<Vec<_>>>()?;
+    entries
+        .into_par_iter()
+        .filter(|e| !e.file_type().is_dir())
+        .map(|e| -> Result<(String, String)> {
+            let relative = e
+                .path()
+                .strip_prefix(dir)?
+                .to_string_lossy()
+                .replace('\\', "/");
+            let hash = match e.path_is_symlink() {
+                true => format!("symlink:{}", e.path().read_link()?.display()),
+                false => format!("sha256:{}", file_hash_sha256(e.path())?),
+            };
+            Ok((relative, hash))
+        })
+        .collect()
+}
+
+fn compare(expected: &BTreeMap<String, String>, actual: &BTreeMap<String, String>) -> Verification {
+    let mut verification = Verification::default();
+    for (path, hash) in expected {
+        match actual.get(path) {
+            Some(actual) if actual != hash => verification.modified.push(path.clone()),
+            Some(_) => {}
+            None => verification.missing.push(path.clone()),
+        }
+    }
+    verification.extra = actual
+        .keys()
+        .filter(|path| !expected.contains_key(*path))
+        .cloned()
+        .collect();
+    verification
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_verify() {
+        let dir = tempfile::tempdir().unwrap();
+        file::create_dir_all(dir.path().join("bin")).unwrap();
+        file::write(dir.path().join("bin/tool"), "v1").unwrap();
+        file::write(dir.path().join("README"), "readme").unwrap();
+        file::create_dir_all(dir.path().join("lib/__pycache__")).unwrap();
+        file::write(dir.path().join("lib/__pycache__/mod.pyc"), "").unwrap();
+        let expected = hash_dir(dir.path()).unwrap();
+        assert_eq!(
+            expected.keys().collect::<Vec<_>>(),
+            vec!["README", "bin/tool"]
+        );
+
+        file::write(dir.path().join("bin/tool"), "v2").unwrap();
+        file::remove_file(dir.path().join("README")).unwrap();
+        file::write(dir.path().join("bin/other"), "").unwrap();
+        let actual = hash_dir(dir.path()).unwrap();
+        assert_eq!(
+            compare(&expected, &actual),
+            Verification {
+                modified: vec!["bin/tool".into()],
+                missing: vec!["README".into()],
+                extra: vec!["bin/other".into()],
+            }
+        );
+        assert!(compare(&actual, &actual).is_ok());
+    }
+}
@@ -0,0 +1,324 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use eyre::{bail, Result};
+use once_cell::sync::Lazy;
+use serde_derive::{Deserialize, Serialize};
+
+use crate::backend::Backend;
+use crate::cli::version::{ARCH, OS};
+use crate::config::Settings;
+use crate::file::display_path;
+use crate::toolset::{ToolRequest, ToolSource, ToolVersion, Toolset};
+use crate::{file, hash};
+
+pub const LOCKFILE_FILENAME: &str = "mise.lock";
+
+/// resolved versions of every tool in a config file, stored in `mise.lock` next to it
+///
+/// ```toml
+/// [[tools.node]]
+/// request = "20"
+/// version = "20.11.1"
+/// backend = "core:node"
+///
+/// [tools.node.platforms.linux-x64]
+/// url = "https://nodejs.org/dist/v20.11.1/node-v20.11.1-linux-x64.tar.gz"
+/// sha256 = "..."
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lockfile {
+    #[serde(default)]
+    tools: BTreeMap<String, Vec<LockfileTool>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockfileTool {
+    /// the version as written in the config file, e.g.: "20" or "latest"
+    pub request: String,
+    /// the exact version it resolved to, e.g.: "20.11.1"
+    pub version: String,
+    pub backend: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub platforms: BTreeMap<String, LockfilePlatform>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LockfilePlatform {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+static LOCKFILES: Lazy<Mutex<HashMap<PathBuf, Lockfile>>> = Lazy::new(Default::default);
+
+/// downloads made during this run which have not been written to a lockfile yet
+static DOWNLOADS: Lazy<Mutex<HashMap<(String, String), LockfilePlatform>>> =
+    Lazy::new(Default::default);
+
+impl Lockfile {
+    fn read(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Default::default());
+        }
+        trace!("reading lockfile {}", display_path(path));
+        let raw = file::read_to_string(path)?;
+        let lockfile = toml::from_str(&raw)?;
+        Ok(lockfile)
+    }
+
+    fn write(&self, path: &Path) -> Result<()> {
+        debug!("writing lockfile {}", display_path(path));
+        let raw = toml::to_string_pretty(self)?;
+        file::write(path, raw)
+    }
+
+    fn get(&self, short: &str, request: &str) -> Option<&LockfileTool> {
+        self.tools
+            .get(short)
+            .and_then(|tools| tools.iter().find(|t| t.request == request))
+    }
+
+    fn upsert(&mut self, short: &str, tool: LockfileTool) {
+        let tools = self.tools.entry(short.to_string()).or_default();
+        match tools.iter_mut().find(|t| t.request == tool.request) {
+            Some(existing) => {
+                let mut platforms = match existing.version == tool.version {
+                    true => existing.platforms.clone(),
+                    false => Default::default(),
+                };
+                platforms.extend(tool.platforms);
+                *existing = LockfileTool { platforms, ..tool };
+            }
+            None => tools.push(tool),
+        }
+    }
+}
+
+pub fn is_enabled() -> bool {
+    let settings = Settings::get();
+    settings.lockfile && settings.experimental
+}
+
+/// returns the lockfile that belongs to a config file
+/// e.g.: ~/src/foo/.mise.toml -> ~/src/foo/mise.lock
+pub fn lockfile_path(source: &ToolSource) -> Option<PathBuf> {
+    match source {
+        ToolSource::MiseToml(path) | ToolSource::ToolVersions(path) => {
+            path.parent().map(|dir| dir.join(LOCKFILE_FILENAME))
+        }
+        _ => None,
+    }
+}
+
+/// key used for per-platform entries, e.g.: "linux-x64" or "macos-arm64"
+pub fn platform_key() -> String {
+    format!("{}-{}", *OS, *ARCH)
+}
+
+fn with_lockfile<T>(path: &Path, f: impl FnOnce(&mut Lockfile) -> T) -> Result<T> {
+    let mut lockfiles = LOCKFILES.lock().unwrap();
+    if !lockfiles.contains_key(path) {
+        lockfiles.insert(path.to_path_buf(), Lockfile::read(path)?);
+    }
+    Ok(f(lockfiles.get_mut(path).unwrap()))
+}
+
+/// looks up the version a request was locked to in the lockfile next to its config file
+pub fn get_locked_version(source: &ToolSource, tr: &ToolRequest) -> Result<Option<String>> {
+    if !is_enabled() {
+        return Ok(None);
+    }
+    if matches!(tr, ToolRequest::Path(..) | ToolRequest::System(_)) {
+        return Ok(None);
+    }
+    let path = match lockfile_path(source) {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    with_lockfile(&path, |lockfile| {
+        lockfile
+            .get(&tr.backend().short, &tr.version())
+            .map(|t| t.version.clone())
+    })
+}
+
+/// resolves a request to the version in the lockfile, falling back to normal resolution
+/// unless `locked` is set in which case it is an error for the request to not be locked
+pub fn resolve(
+    backend: &dyn Backend,
+    source: &ToolSource,
+    tr: &ToolRequest,
+    latest_versions: bool,
+    locked: bool,
+) -> Result<ToolVersion> {
+    if let Some(version) = get_locked_version(source, tr)? {
+        return Ok(ToolVersion::new(backend, tr.clone(), version));
+    }
+    if locked && !matches!(tr, ToolRequest::Path(..) | ToolRequest::System(_)) {
+        bail!(
+            "{tr} is not in {}, run `mise install` without --locked to update it",
+            LOCKFILE_FILENAME
+        );
+    }
+    tr.resolve(backend, latest_versions)
+}
+
+/// records the url and checksum of a file downloaded for a tool version so it can be written
+/// to the lockfile and the install manifest. If the lockfile already has a checksum for this
+/// platform it must match.
+pub fn record_download(tv: &ToolVersion, url: &str, path: &Path) -> Result<()> {
+    let sha256 = hash::file_hash_sha256(path)?;
+    let key = (tv.backend.short.clone(), tv.version.clone());
+    if let Some(expected) = is_enabled().then(|| locked_checksum(tv)).flatten() {
+        if expected != sha256 {
+            bail!(
+                "Checksum mismatch for {tv} in {LOCKFILE_FILENAME}:\nExpected: {expected}\nActual:   {sha256}\nURL:      {url}",
+            );
+        }
+    }
+    let platform = LockfilePlatform {
+        url: Some(url.to_string()),
+        sha256: Some(sha256),
+    };
+    DOWNLOADS.lock().unwrap().insert(key, platform);
+    Ok(())
+}
+
+/// the url and checksum a tool version was downloaded with on this platform according to the
+/// lockfile next to the config file that requested it
+pub fn get_locked_platform(
+    source: &ToolSource,
+    tv: &ToolVersion,
+) -> Result<Option<LockfilePlatform>> {
+    let path = match lockfile_path(source) {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+    let platform = platform_key();
+    with_lockfile(&path, |lockfile| {
+        lockfile
+            .tools
+            .get(&tv.backend.short)
+            .into_iter()
+            .flatten()
+            .filter(|t| t.version == tv.version)
+            .find_map(|t| t.platforms.get(&platform).cloned())
+    })
+}
+
+/// the url and checksum recorded by `record_download` during this run
+pub fn get_download(tv: &ToolVersion) -> Option<LockfilePlatform> {
+    let key = (tv.backend.short.clone(), tv.version.clone());
+    DOWNLOADS.lock().unwrap().get(&key).cloned()
+}
+
+fn locked_checksum(tv: &ToolVersion) -> Option<String> {
+    let platform = platform_key();
+    LOCKFILES
+        .lock()
+        .unwrap()
+        .values()
+        .flat_map(|l| l.tools.get(&tv.backend.short))
+        .flatten()
+        .filter(|t| t.version == tv.version)
+        .find_map(|t| t.platforms.get(&platform).and_then(|p| p.sha256.clone()))
+}
+
+/// writes the resolved versions in the toolset to the lockfiles next to their config files
+pub fn update_lockfiles(ts: &Toolset) -> Result<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+    let downloads = DOWNLOADS.lock().unwrap().clone();
+    let mut updated: BTreeMap<PathBuf, Lockfile> = BTreeMap::new();
+    for tvl in ts.versions.values() {
+        let path = match lockfile_path(&tvl.source) {
+            Some(path) => path,
+            None => continue,
+        };
+        if !updated.contains_key(&path) {
+            let lockfile = with_lockfile(&path, |l| l.clone())?;
+            updated.insert(path.clone(), lockfile);
+        }
+        let lockfile = updated.get_mut(&path).unwrap();
+        for (tr, tv) in tvl.requests.iter().zip(tvl.versions.iter()) {
+            if matches!(tr, ToolRequest::Path(..) | ToolRequest::System(_)) {
+                continue;
+            }
+            let mut platforms = BTreeMap::new();
+            if let Some(p) = downloads.get(&(tv.backend.short.clone(), tv.version.clone())) {
+                platforms.insert(platform_key(), p.clone());
+            }
+            let tool = LockfileTool {
+                request: tr.version(),
+                version: tv.version.clone(),
+                backend: tv.backend.full.clone(),
+                platforms,
+            };
+            lockfile.upsert(&tvl.backend.short, tool);
+        }
+    }
+    for (path, lockfile) in updated {
+        let changed = with_lockfile(&path, |l| {
+            let changed = *l != lockfile;
+            *l = lockfile.clone();
+            changed
+        })?;
+        if changed {
+            lockfile.write(&path)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_lockfile_upsert() {
+        let tool = |request: &str, version: &str, sha256: Option<&str>| LockfileTool {
+            request: request.into(),
+            version: version.into(),
+            backend: "core:node".into(),
+            platforms: sha256
+                .map(|s| {
+                    let p = LockfilePlatform {
+                        url: None,
+                        sha256: Some(s.into()),
+                    };
+                    [(platform_key(), p)].into()
+                })
+                .unwrap_or_default(),
+        };
+        let mut lockfile = Lockfile::default();
+        lockfile.upsert("node", tool("20", "20.0.0", Some("abc")));
+        lockfile.upsert("node", tool("18", "18.0.0", None));
+        // same version keeps the checksums
+        lockfile.upsert("node", tool("20", "20.0.0", None));
+        assert_eq!(
+            lockfile.get("node", "20"),
+            Some(&tool("20", "20.0.0", Some("abc")))
+        );
+        // a new version drops them
+        lockfile.upsert("node", tool("20", "20.1.0", None));
+        assert_eq!(
+            lockfile.get("node", "20"),
+            Some(&tool("20", "20.1.0", None))
+        );
+        assert_eq!(
+            lockfile.get("node", "18"),
+            Some(&tool("18", "18.0.0", None))
+        );
+        assert_eq!(lockfile.get("node", "22"), None);
+
+        let raw = toml::to_string_pretty(&lockfile).unwrap();
+        let parsed: Lockfile = toml::from_str(&raw).unwrap();
+        assert_eq!(parsed, lockfile);
+    }
+}
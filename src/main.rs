@@ -22,15 +22,18 @@ mod cmd;
 
 mod backend;
 pub(crate) mod build_time;
+mod bundle;
 mod cache;
 mod cli;
 mod config;
 mod default_shorthands;
 mod direnv;
 mod dirs;
+mod download_cache;
 pub(crate) mod duration;
 mod env;
 mod env_diff;
+mod eol;
 mod errors;
 #[cfg_attr(windows, path = "fake_asdf_windows.rs")]
 mod fake_asdf;
@@ -41,7 +44,9 @@ mod hash;
 mod hook_env;
 mod http;
 mod install_context;
+mod install_manifest;
 mod lock_file;
+mod lockfile;
 mod logger;
 mod migrate;
 mod path_env;
@@ -50,6 +55,7 @@ mod rand;
 mod registry;
 pub(crate) mod result;
 mod runtime_symlinks;
+mod sbom;
 mod shell;
 mod shims;
 mod shorthands;
@@ -1,7 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use contracts::requires;
-use eyre::Result;
+use eyre::{bail, Result};
 use itertools::Itertools;
 use versions::Versioning;
 
@@ -9,13 +9,14 @@ use crate::backend::Backend;
 use crate::cli::args::BackendArg;
 use crate::cli::version::{ARCH, OS};
 use crate::cmd::CmdLineRunner;
-use crate::file;
+use crate::config::Settings;
 use crate::github::GithubRelease;
 use crate::http::{HTTP, HTTP_FETCH};
 use crate::install_context::InstallContext;
 use crate::plugins::core::CorePlugin;
 use crate::toolset::{ToolRequest, ToolVersion};
 use crate::ui::progress_report::SingleReport;
+use crate::{download_cache, file, hash, lockfile};
 
 #[derive(Debug)]
 pub struct BunPlugin {
@@ -69,7 +70,22 @@ impl BunPlugin {
         let tarball_path = tv.download_path().join(filename);
 
         pr.set_message(format!("downloading {filename}"));
-        HTTP.download_file(&url, &tarball_path, Some(pr))?;
+        download_cache::download(&url, &tarball_path, Some(pr), |tarball_path| {
+            if !Settings::get().bun_skip_checksum {
+                pr.set_message(format!("verifying {filename}"));
+                let shasums_url = format!(
+                    "https://github.com/oven-sh/bun/releases/download/bun-v{}/SHASUMS256.txt",
+                    tv.version
+                );
+                let shasums = HTTP.get_text(&shasums_url)?;
+                let Some(checksum) = hash::parse_checksum(&shasums, filename) else {
+                    bail!("no checksum found for {filename} in {shasums_url}");
+                };
+                hash::ensure_checksum_sha256(tarball_path, &checksum, Some(pr))?;
+            }
+            Ok(())
+        })?;
+        lockfile::record_download(tv, &url, &tarball_path)?;
 
         Ok(tarball_path)
     }
@@ -113,7 +129,7 @@ impl Backend for BunPlugin {
         Ok(vec![".bun-version".into()])
     }
 
-    #[requires(matches!(ctx.tv.request, ToolRequest::Version { .. } | ToolRequest::Prefix { .. }), "unsupported tool version request type")]
+    #[requires(matches!(ctx.tv.request, ToolRequest::Version { .. } | ToolRequest::Prefix { .. } | ToolRequest::Range { .. }), "unsupported tool version request type")]
     fn install_version_impl(&self, ctx: &InstallContext) -> Result<()> {
         let tarball_path = self.download(&ctx.tv, ctx.pr.as_ref())?;
         self.install(ctx, &tarball_path)?;
@@ -2,7 +2,7 @@ use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 
 use contracts::requires;
-use eyre::Result;
+use eyre::{bail, Result};
 use itertools::Itertools;
 use versions::Versioning;
 
@@ -10,14 +10,14 @@ use crate::backend::Backend;
 use crate::cli::args::BackendArg;
 use crate::cli::version::{ARCH, OS};
 use crate::cmd::CmdLineRunner;
-use crate::config::Config;
-use crate::file;
+use crate::config::{Config, Settings};
 use crate::github::GithubRelease;
 use crate::http::{HTTP, HTTP_FETCH};
 use crate::install_context::InstallContext;
 use crate::plugins::core::CorePlugin;
 use crate::toolset::{ToolRequest, ToolVersion, Toolset};
 use crate::ui::progress_report::SingleReport;
+use crate::{download_cache, file, hash, http, lockfile};
 
 #[derive(Debug)]
 pub struct DenoPlugin {
@@ -72,9 +72,26 @@ impl DenoPlugin {
         let tarball_path = tv.download_path().join(filename);
 
         pr.set_message(format!("downloading {filename}"));
-        HTTP.download_file(&url, &tarball_path, Some(pr))?;
-
-        // TODO: hash::ensure_checksum_sha256(&tarball_path, &m.sha256)?;
+        download_cache::download(&url, &tarball_path, Some(pr), |tarball_path| {
+            if !Settings::get().deno_skip_checksum {
+                pr.set_message(format!("verifying {filename}"));
+                // only published since deno 1.43
+                match HTTP.get_text(format!("{url}.sha256sum")) {
+                    Ok(checksum) => {
+                        let Some(checksum) = hash::parse_checksum(&checksum, filename) else {
+                            bail!("no checksum found for {filename} in {url}.sha256sum");
+                        };
+                        hash::ensure_checksum_sha256(tarball_path, &checksum, Some(pr))?;
+                    }
+                    Err(err) if http::error_code(&err) == Some(404) => {
+                        debug!("no checksum published for {filename}");
+                    }
+                    Err(err) => return Err(err),
+                }
+            }
+            Ok(())
+        })?;
+        lockfile::record_download(tv, &url, &tarball_path)?;
 
         Ok(tarball_path)
     }
@@ -111,7 +128,7 @@ impl Backend for DenoPlugin {
         Ok(vec![".deno-version".into()])
     }
 
-    #[requires(matches!(ctx.tv.request, ToolRequest::Version { .. } | ToolRequest::Prefix { .. }), "unsupported tool version request type")]
+    #[requires(matches!(ctx.tv.request, ToolRequest::Version { .. } | ToolRequest::Prefix { .. } | ToolRequest::Range { .. }), "unsupported tool version request type")]
     fn install_version_impl(&self, ctx: &InstallContext) -> Result<()> {
         let tarball_path = self.download(&ctx.tv, ctx.pr.as_ref())?;
         self.install(&ctx.tv, ctx.pr.as_ref(), &tarball_path)?;
@@ -103,6 +103,10 @@ impl Backend for ErlangPlugin {
             .cloned()
     }
 
+    /// kerl writes the install path into the erl scripts
+    fn is_relocatable(&self) -> bool {
+        false
+    }
     fn install_version_impl(&self, ctx: &InstallContext) -> Result<()> {
         self.update_kerl()?;
 
@@ -1,6 +1,5 @@
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
-use std::thread;
 
 use itertools::Itertools;
 use tempfile::tempdir_in;
@@ -16,7 +15,7 @@ use crate::install_context::InstallContext;
 use crate::plugins::core::CorePlugin;
 use crate::toolset::{ToolRequest, ToolVersion, Toolset};
 use crate::ui::progress_report::SingleReport;
-use crate::{cmd, env, file, hash};
+use crate::{cmd, download_cache, env, file, hash, lockfile};
 
 #[derive(Debug)]
 pub struct GoPlugin {
@@ -117,21 +116,17 @@ impl GoPlugin {
         let tarball_url = format!("{}/{}", &settings.go_download_mirror, &filename);
         let tarball_path = tv.download_path().join(&filename);
 
-        thread::scope(|s| {
-            let checksum_handle = s.spawn(|| {
-                let checksum_url = format!("{}.sha256", &tarball_url);
-                HTTP.get_text(checksum_url)
-            });
-            pr.set_message(format!("downloading {filename}"));
-            HTTP.download_file(&tarball_url, &tarball_path, Some(pr))?;
-
+        pr.set_message(format!("downloading {filename}"));
+        download_cache::download(&tarball_url, &tarball_path, Some(pr), |tarball_path| {
             if !settings.go_skip_checksum {
                 pr.set_message(format!("verifying {filename}"));
-                let checksum = checksum_handle.join().unwrap()?;
-                hash::ensure_checksum_sha256(&tarball_path, &checksum, Some(pr))?;
+                let checksum = HTTP.get_text(format!("{}.sha256", &tarball_url))?;
+                hash::ensure_checksum_sha256(tarball_path, &checksum, Some(pr))?;
             }
-            Ok(tarball_path)
-        })
+            Ok(())
+        })?;
+        lockfile::record_download(tv, &tarball_url, &tarball_path)?;
+        Ok(tarball_path)
     }
 
     fn install(
@@ -12,19 +12,19 @@ use regex::Regex;
 use serde_derive::{Deserialize, Serialize};
 use versions::Versioning;
 
+use crate::backend::version_info::VersionInfo;
 use crate::backend::Backend;
 use crate::cache::CacheManager;
 use crate::cli::args::BackendArg;
 use crate::cli::version::{ARCH, OS};
 use crate::cmd::CmdLineRunner;
 use crate::config::Config;
-use crate::http::{HTTP, HTTP_FETCH};
+use crate::http::HTTP_FETCH;
 use crate::install_context::InstallContext;
 use crate::plugins::core::CorePlugin;
-use crate::plugins::VERSION_REGEX;
-use crate::toolset::{ToolRequest, ToolVersion, Toolset};
+use crate::toolset::{ToolRequest, ToolVersion, Toolset, VersionRange};
 use crate::ui::progress_report::SingleReport;
-use crate::{env, file, hash};
+use crate::{download_cache, env, file, hash, lockfile};
 
 #[derive(Debug)]
 pub struct JavaPlugin {
@@ -138,9 +138,10 @@ impl JavaPlugin {
         let tarball_path = tv.download_path().join(filename);
 
         pr.set_message(format!("downloading {filename}"));
-        HTTP.download_file(&m.url, &tarball_path, Some(pr))?;
-
-        hash::ensure_checksum_sha256(&tarball_path, &m.sha256, Some(pr))?;
+        download_cache::download(&m.url, &tarball_path, Some(pr), |tarball_path| {
+            hash::ensure_checksum_sha256(tarball_path, &m.sha256, Some(pr))
+        })?;
+        lockfile::record_download(tv, &m.url, &tarball_path)?;
 
         Ok(tarball_path)
     }
@@ -207,7 +208,7 @@ impl JavaPlugin {
                 file::rename(entry.path(), dest)?;
             }
             file::make_symlink(
-                tv.install_path().as_path(),
+                Path::new(".."),
                 &tv.install_path().join("Contents").join("Home"),
             )?;
         }
@@ -219,10 +220,7 @@ impl JavaPlugin {
                 .split_once('.')
                 .unwrap_or_else(|| (&m.version, ""));
             file::make_symlink(
-                tv.install_path()
-                    .join(format!("zulu-{}.jdk", major_version))
-                    .join("Contents")
-                    .as_path(),
+                &Path::new(&format!("zulu-{}.jdk", major_version)).join("Contents"),
                 &tv.install_path().join("Contents"),
             )?;
         }
@@ -313,13 +311,32 @@ impl Backend for JavaPlugin {
             .cloned()
     }
 
+    fn _list_remote_versions_info(&self) -> Result<Vec<VersionInfo>> {
+        let metadata = self.fetch_java_metadata("ga")?;
+        let versions = self
+            ._list_remote_versions()?
+            .into_iter()
+            .map(|v| match metadata.get(&v) {
+                Some(m) => VersionInfo {
+                    channel: Some(m.release_type.clone()),
+                    lts: java_lts(&m.version),
+                    prerelease: m.release_type != "ga",
+                    version: v,
+                    ..Default::default()
+                },
+                None => VersionInfo::new(v),
+            })
+            .collect();
+        Ok(versions)
+    }
+
     fn list_installed_versions_matching(&self, query: &str) -> eyre::Result<Vec<String>> {
         let versions = self.list_installed_versions()?;
-        fuzzy_match_filter(versions, query)
+        fuzzy_match_filter(versions.into_iter().map(VersionInfo::new).collect(), query)
     }
 
     fn list_versions_matching(&self, query: &str) -> eyre::Result<Vec<String>> {
-        let versions = self.list_remote_versions()?;
+        let versions = self.list_remote_versions_info()?;
         fuzzy_match_filter(versions, query)
     }
 
@@ -369,7 +386,7 @@ impl Backend for JavaPlugin {
         }
     }
 
-    #[requires(matches!(ctx.tv.request, ToolRequest::Version { .. } | ToolRequest::Prefix { .. }), "unsupported tool version request type")]
+    #[requires(matches!(ctx.tv.request, ToolRequest::Version { .. } | ToolRequest::Prefix { .. } | ToolRequest::Range { .. }), "unsupported tool version request type")]
     fn install_version_impl(&self, ctx: &InstallContext) -> Result<()> {
         let metadata = self.tv_to_metadata(&ctx.tv)?;
         let tarball_path = self.download(&ctx.tv, ctx.pr.as_ref(), metadata)?;
@@ -413,7 +430,25 @@ fn arch() -> &'static str {
     }
 }
 
-fn fuzzy_match_filter(versions: Vec<String>, query: &str) -> eyre::Result<Vec<String>> {
+/// the lts line of a java version, e.g.: "21" for "21.0.1+12"
+fn java_lts(version: &str) -> Option<String> {
+    let major = version.split(['.', '+']).next()?;
+    match major {
+        "8" | "11" | "17" | "21" | "25" => Some(major.to_string()),
+        _ => None,
+    }
+}
+
+fn fuzzy_match_filter(versions: Vec<VersionInfo>, query: &str) -> eyre::Result<Vec<String>> {
+    if VersionRange::is_range(query) {
+        let range = VersionRange::parse(query)?;
+        // build metadata like the "+12" in "21.0.1+12" doesn't count for ranges
+        return Ok(versions
+            .into_iter()
+            .filter(|v| !v.yanked && range.matches(v.version.split('+').next().unwrap()))
+            .map(|v| v.version)
+            .collect());
+    }
     let mut query = query;
     if query == "latest" {
         query = "[0-9].*";
@@ -422,14 +457,15 @@ fn fuzzy_match_filter(versions: Vec<String>, query: &str) -> eyre::Result<Vec<St
     let versions = versions
         .into_iter()
         .filter(|v| {
-            if query == v {
+            if query == v.version {
                 return true;
             }
-            if VERSION_REGEX.is_match(v) {
+            if v.is_hidden() {
                 return false;
             }
-            query_regex.is_match(v)
+            query_regex.is_match(&v.version)
         })
+        .map(|v| v.version)
         .collect();
     Ok(versions)
 }
@@ -489,3 +525,22 @@ static JAVA_FEATURES: Lazy<HashSet<String>> =
     Lazy::new(|| HashSet::from(["musl", "javafx", "lite", "large_heap"].map(|s| s.to_string())));
 static JAVA_FILE_TYPES: Lazy<HashSet<String>> =
     Lazy::new(|| HashSet::from(["tar.gz"].map(|s| s.to_string())));
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_match_filter_range() {
+        let versions = ["11.0.2", "17.0.2", "21.0.1+12", "22.0.1", "openjdk-21.0.1"]
+            .into_iter()
+            .map(VersionInfo::new)
+            .collect();
+        assert_eq!(
+            fuzzy_match_filter(versions, ">=17,<22").unwrap(),
+            vec!["17.0.2", "21.0.1+12"]
+        );
+    }
+}
@@ -6,6 +6,7 @@ use std::sync::Arc;
 
 pub use python::PythonPlugin;
 
+use crate::backend::version_info::VersionInfo;
 use crate::backend::{Backend, BackendMap};
 use crate::cache::CacheManager;
 use crate::cli::args::BackendArg;
@@ -89,6 +90,7 @@ pub static CORE_PLUGINS: Lazy<BackendMap> = Lazy::new(|| {
 pub struct CorePlugin {
     pub fa: BackendArg,
     pub remote_version_cache: CacheManager<Vec<String>>,
+    pub remote_version_info_cache: CacheManager<Vec<VersionInfo>>,
 }
 
 impl CorePlugin {
@@ -107,6 +109,10 @@ impl CorePlugin {
                 fa.cache_path.join("remote_versions-$KEY.msgpack.z"),
             )
             .with_fresh_duration(*env::MISE_FETCH_REMOTE_VERSIONS_CACHE),
+            remote_version_info_cache: CacheManager::new(
+                fa.cache_path.join("remote_versions_info-$KEY.msgpack.z"),
+            )
+            .with_fresh_duration(*env::MISE_FETCH_REMOTE_VERSIONS_CACHE),
             fa,
         }
     }
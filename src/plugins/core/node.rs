@@ -6,6 +6,7 @@ use serde_derive::Deserialize;
 use tempfile::tempdir_in;
 use url::Url;
 
+use crate::backend::version_info::VersionInfo;
 use crate::backend::Backend;
 use crate::build_time::built_info;
 use crate::cli::args::BackendArg;
@@ -17,7 +18,7 @@ use crate::install_context::InstallContext;
 use crate::plugins::core::CorePlugin;
 use crate::toolset::ToolVersion;
 use crate::ui::progress_report::SingleReport;
-use crate::{env, file, hash, http};
+use crate::{download_cache, env, file, hash, http, lockfile};
 
 #[derive(Debug)]
 pub struct NodePlugin {
@@ -60,6 +61,16 @@ impl NodePlugin {
         Ok(versions)
     }
 
+    fn fetch_remote_versions_info(&self) -> Result<Vec<VersionInfo>> {
+        let versions = HTTP_FETCH
+            .json::<Vec<NodeVersion>, _>(MISE_NODE_MIRROR_URL.join("index.json")?)?
+            .into_iter()
+            .map(|v| v.into())
+            .rev()
+            .collect();
+        Ok(versions)
+    }
+
     fn install_precompiled(&self, ctx: &InstallContext, opts: &BuildOpts) -> Result<()> {
         match self.fetch_tarball(
             ctx.pr.as_ref(),
@@ -73,6 +84,11 @@ impl NodePlugin {
             }
             e => e,
         }?;
+        lockfile::record_download(
+            &ctx.tv,
+            opts.binary_tarball_url.as_str(),
+            &opts.binary_tarball_path,
+        )?;
         let tarball_name = &opts.binary_tarball_name;
         ctx.pr.set_message(format!("extracting {tarball_name}"));
         let tmp_extract_path = tempdir_in(opts.install_path.parent().unwrap())?;
@@ -95,6 +111,11 @@ impl NodePlugin {
             }
             e => e,
         }?;
+        lockfile::record_download(
+            &ctx.tv,
+            opts.binary_tarball_url.as_str(),
+            &opts.binary_tarball_path,
+        )?;
         let tarball_name = &opts.binary_tarball_name;
         ctx.pr.set_message(format!("extracting {tarball_name}"));
         let tmp_extract_path = tempdir_in(opts.install_path.parent().unwrap())?;
@@ -113,6 +134,11 @@ impl NodePlugin {
             &opts.source_tarball_path,
             &opts.version,
         )?;
+        lockfile::record_download(
+            &ctx.tv,
+            opts.source_tarball_url.as_str(),
+            &opts.source_tarball_path,
+        )?;
         ctx.pr.set_message(format!("extracting {tarball_name}"));
         file::remove_all(&opts.build_dir)?;
         file::untar(&opts.source_tarball_path, opts.build_dir.parent().unwrap())?;
@@ -130,17 +156,20 @@ impl NodePlugin {
         version: &str,
     ) -> Result<()> {
         let tarball_name = local.file_name().unwrap().to_string_lossy().to_string();
+        let verify = |local: &Path| {
+            if *env::MISE_NODE_VERIFY {
+                pr.set_message(format!("verifying {tarball_name}"));
+                self.verify(local, version, pr)?;
+            }
+            Ok(())
+        };
         if local.exists() {
             pr.set_message(format!("using previously downloaded {tarball_name}"));
+            verify(local)
         } else {
             pr.set_message(format!("downloading {tarball_name}"));
-            HTTP.download_file(url.clone(), local, Some(pr))?;
+            download_cache::download(url.as_str(), local, Some(pr), verify)
         }
-        if *env::MISE_NODE_VERIFY {
-            pr.set_message(format!("verifying {tarball_name}"));
-            self.verify(local, version, pr)?;
-        }
-        Ok(())
     }
 
     fn sh<'a>(&'a self, ctx: &'a InstallContext, opts: &BuildOpts) -> eyre::Result<CmdLineRunner> {
@@ -167,9 +196,21 @@ impl NodePlugin {
 
     fn verify(&self, tarball: &Path, version: &str, pr: &dyn SingleReport) -> Result<()> {
         let tarball_name = tarball.file_name().unwrap().to_string_lossy().to_string();
-        // TODO: verify gpg signature
-        let shasums = HTTP.get_text(self.shasums_url(version)?)?;
-        let shasums = hash::parse_shasums(&shasums);
+        let shasums_url = self.shasums_url(version)?;
+        let shasums_path = tarball.with_file_name("SHASUMS256.txt");
+        HTTP.download_file(shasums_url.clone(), &shasums_path, None)?;
+        let sig_path = tarball.with_file_name("SHASUMS256.txt.sig");
+        let sig = if !hash::has_trusted_keys("node") {
+            None
+        } else {
+            match HTTP.download_file(format!("{shasums_url}.sig"), &sig_path, None) {
+                Ok(()) => Some(hash::Signature::Gpg(&sig_path)),
+                Err(err) if http::error_code(&err) == Some(404) => None,
+                Err(err) => return Err(err),
+            }
+        };
+        hash::verify_signature("node", &shasums_path, sig)?;
+        let shasums = hash::parse_shasums(&file::read_to_string(&shasums_path)?);
         let shasum = shasums.get(&tarball_name).unwrap();
         hash::ensure_checksum_sha256(tarball, shasum, Some(pr))
     }
@@ -262,7 +303,6 @@ impl NodePlugin {
     }
 
     fn shasums_url(&self, v: &str) -> Result<Url> {
-        // let url = MISE_NODE_MIRROR_URL.join(&format!("v{v}/SHASUMS256.txt.asc"))?;
         let url = MISE_NODE_MIRROR_URL.join(&format!("v{v}/SHASUMS256.txt"))?;
         Ok(url)
     }
@@ -280,6 +320,13 @@ impl Backend for NodePlugin {
             .cloned()
     }
 
+    fn _list_remote_versions_info(&self) -> Result<Vec<VersionInfo>> {
+        self.core
+            .remote_version_info_cache
+            .get_or_try_init(|| self.fetch_remote_versions_info())
+            .cloned()
+    }
+
     fn get_aliases(&self) -> Result<BTreeMap<String, String>> {
         let aliases = [
             ("lts/argon", "4"),
@@ -458,4 +505,23 @@ fn arch() -> &'static str {
 #[derive(Debug, Deserialize)]
 struct NodeVersion {
     version: String,
+    date: String,
+    /// false or the name of the lts line, e.g.: "Iron"
+    lts: serde_json::Value,
+}
+
+impl From<NodeVersion> for VersionInfo {
+    fn from(v: NodeVersion) -> Self {
+        let lts = v.lts.as_str().map(|s| s.to_string());
+        Self {
+            version: match regex!(r"^v\d+\.").is_match(&v.version) {
+                true => v.version.strip_prefix('v').unwrap().to_string(),
+                false => v.version,
+            },
+            channel: Some(if lts.is_some() { "lts" } else { "current" }.to_string()),
+            release_date: Some(v.date),
+            lts,
+            ..Default::default()
+        }
+    }
 }
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use eyre::{bail, eyre};
 use itertools::Itertools;
 
+use crate::backend::version_info::VersionInfo;
 use crate::backend::Backend;
 use crate::build_time::built_info;
 use crate::cache::CacheManager;
@@ -17,7 +18,7 @@ use crate::install_context::InstallContext;
 use crate::plugins::core::CorePlugin;
 use crate::toolset::{ToolRequest, ToolVersion, Toolset};
 use crate::ui::progress_report::SingleReport;
-use crate::{cmd, env, file};
+use crate::{cmd, download_cache, env, file, hash, lockfile};
 
 #[derive(Debug)]
 pub struct PythonPlugin {
@@ -164,7 +165,18 @@ impl PythonPlugin {
         let tarball_path = download.join(filename);
 
         ctx.pr.set_message(format!("downloading {filename}"));
-        HTTP.download_file(&url, &tarball_path, Some(ctx.pr.as_ref()))?;
+        download_cache::download(&url, &tarball_path, Some(ctx.pr.as_ref()), |tarball_path| {
+            if !Settings::get().python_precompiled_skip_checksum {
+                ctx.pr.set_message(format!("verifying {filename}"));
+                let checksum = HTTP.get_text(format!("{url}.sha256"))?;
+                let Some(checksum) = hash::parse_checksum(&checksum, filename) else {
+                    bail!("no checksum found for {filename} in {url}.sha256");
+                };
+                hash::ensure_checksum_sha256(tarball_path, &checksum, Some(ctx.pr.as_ref()))?;
+            }
+            Ok(())
+        })?;
+        lockfile::record_download(&ctx.tv, &url, &tarball_path)?;
 
         ctx.pr.set_message(format!("installing {filename}"));
         file::untar(&tarball_path, &download)?;
@@ -337,6 +349,39 @@ impl Backend for PythonPlugin {
         }
     }
 
+    fn _list_remote_versions_info(&self) -> eyre::Result<Vec<VersionInfo>> {
+        // precompiled versions are published with python-build-standalone releases which are
+        // tagged with the date, e.g.: "20240107"
+        let mut release_dates = BTreeMap::new();
+        if Settings::get().python_compile == Some(false) {
+            for (v, tag, _) in self.fetch_precompiled_remote_versions()? {
+                if let Some(c) = regex!(r"^(\d{4})(\d{2})(\d{2})$").captures(tag) {
+                    let date = format!("{}-{}-{}", &c[1], &c[2], &c[3]);
+                    let earliest = release_dates.entry(v.clone()).or_insert(date.clone());
+                    if date < *earliest {
+                        *earliest = date;
+                    }
+                }
+            }
+        }
+        let versions = self
+            ._list_remote_versions()?
+            .into_iter()
+            .map(|v| VersionInfo {
+                // cpython or another implementation, e.g.: "pypy" for "pypy3.10-7.3.15"
+                channel: match regex!(r"^[a-z]+").find(&v) {
+                    Some(m) => Some(m.as_str().to_string()),
+                    None => Some("cpython".to_string()),
+                },
+                release_date: release_dates.get(&v).cloned(),
+                prerelease: regex!(r"(\d(a|b|rc)\d+|-dev|-latest|-src)$").is_match(&v),
+                version: v,
+                ..Default::default()
+            })
+            .collect();
+        Ok(versions)
+    }
+
     #[cfg(windows)]
     fn list_bin_paths(&self, tv: &ToolVersion) -> eyre::Result<Vec<PathBuf>> {
         Ok(vec![tv.install_path()])
@@ -346,6 +391,10 @@ impl Backend for PythonPlugin {
         Ok(vec![".python-version".to_string()])
     }
 
+    /// python-build compiles with the install path as prefix and pip writes absolute shebangs
+    fn is_relocatable(&self) -> bool {
+        false
+    }
     fn install_version_impl(&self, ctx: &InstallContext) -> eyre::Result<()> {
         let config = Config::get();
         let settings = Settings::try_get()?;
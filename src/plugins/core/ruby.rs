@@ -358,7 +358,11 @@ impl Backend for RubyPlugin {
         Ok(v)
     }
 
-    #[requires(matches!(ctx.tv.request, ToolRequest::Version { .. } | ToolRequest::Prefix { .. }), "unsupported tool version request type")]
+    /// ruby-build compiles with the install path as prefix
+    fn is_relocatable(&self) -> bool {
+        false
+    }
+    #[requires(matches!(ctx.tv.request, ToolRequest::Version { .. } | ToolRequest::Prefix { .. } | ToolRequest::Range { .. }), "unsupported tool version request type")]
     fn install_version_impl(&self, ctx: &InstallContext) -> Result<()> {
         if let Err(err) = self.update_build_tool() {
             warn!("ruby build tool update error: {err:#}");
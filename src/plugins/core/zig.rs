@@ -9,13 +9,12 @@ use crate::backend::Backend;
 use crate::cli::args::BackendArg;
 use crate::cli::version::{ARCH, OS};
 use crate::cmd::CmdLineRunner;
-use crate::file;
 use crate::github::GithubRelease;
 use crate::http::{HTTP, HTTP_FETCH};
 use crate::install_context::InstallContext;
 use crate::plugins::core::CorePlugin;
 use crate::toolset::{ToolRequest, ToolVersion};
-use crate::ui::progress_report::SingleReport;
+use crate::{download_cache, file, hash, http, lockfile};
 
 #[derive(Debug)]
 pub struct ZigPlugin {
@@ -76,7 +75,8 @@ impl ZigPlugin {
         Ok(versions)
     }
 
-    fn download(&self, tv: &ToolVersion, pr: &dyn SingleReport) -> Result<PathBuf> {
+    fn download(&self, ctx: &InstallContext) -> Result<PathBuf> {
+        let tv = &ctx.tv;
         let url = if tv.version == "ref:master" {
             format!(
                 "https://ziglang.org/builds/zig-{}-{}-{}.tar.xz",
@@ -97,8 +97,11 @@ impl ZigPlugin {
         let filename = url.split('/').last().unwrap();
         let tarball_path = tv.download_path().join(filename);
 
-        pr.set_message(format!("downloading {filename}"));
-        HTTP.download_file(&url, &tarball_path, Some(pr))?;
+        ctx.pr.set_message(format!("downloading {filename}"));
+        download_cache::download(&url, &tarball_path, Some(ctx.pr.as_ref()), |tarball_path| {
+            self.verify(ctx, tarball_path, &url)
+        })?;
+        lockfile::record_download(tv, &url, &tarball_path)?;
 
         Ok(tarball_path)
     }
@@ -107,7 +110,7 @@ impl ZigPlugin {
         let filename = tarball_path.file_name().unwrap().to_string_lossy();
         ctx.pr.set_message(format!("installing {filename}"));
         file::remove_all(ctx.tv.install_path())?;
-        untar_xy(tarball_path, &ctx.tv.download_path())?;
+        file::untar_xy(tarball_path, &ctx.tv.download_path())?;
         file::rename(
             ctx.tv.download_path().join(format!(
                 "zig-{}-{}-{}",
@@ -122,16 +125,25 @@ impl ZigPlugin {
             ctx.tv.install_path(),
         )?;
         file::create_dir_all(ctx.tv.install_path().join("bin"))?;
-        file::make_symlink(
-            self.zig_bin(&ctx.tv).as_path(),
-            &ctx.tv.install_path().join("bin/zig"),
-        )?;
+        file::make_symlink(Path::new("../zig"), &ctx.tv.install_path().join("bin/zig"))?;
 
         Ok(())
     }
 
-    fn verify(&self, ctx: &InstallContext) -> Result<()> {
-        self.test_zig(ctx)
+    fn verify(&self, ctx: &InstallContext, tarball_path: &Path, url: &str) -> Result<()> {
+        let filename = tarball_path.file_name().unwrap().to_string_lossy();
+        ctx.pr.set_message(format!("verifying {filename}"));
+        let sig_path = tarball_path.with_file_name(format!("{filename}.minisig"));
+        let sig = if !hash::has_trusted_keys("zig") {
+            None
+        } else {
+            match HTTP.download_file(format!("{url}.minisig"), &sig_path, None) {
+                Ok(()) => Some(hash::Signature::Minisign(&sig_path)),
+                Err(err) if http::error_code(&err) == Some(404) => None,
+                Err(err) => return Err(err),
+            }
+        };
+        hash::verify_signature("zig", tarball_path, sig)
     }
 
     fn get_master_version(&self) -> Result<String> {
@@ -160,11 +172,11 @@ impl Backend for ZigPlugin {
     fn legacy_filenames(&self) -> Result<Vec<String>> {
         Ok(vec![".zig-version".into()])
     }
-    #[requires(matches ! (ctx.tv.request, ToolRequest::Version { .. } | ToolRequest::Prefix { .. } | ToolRequest::Ref { .. }), "unsupported tool version request type")]
+    #[requires(matches ! (ctx.tv.request, ToolRequest::Version { .. } | ToolRequest::Prefix { .. } | ToolRequest::Range { .. } | ToolRequest::Ref { .. }), "unsupported tool version request type")]
     fn install_version_impl(&self, ctx: &InstallContext) -> Result<()> {
-        let tarball_path = self.download(&ctx.tv, ctx.pr.as_ref())?;
+        let tarball_path = self.download(ctx)?;
         self.install(ctx, &tarball_path)?;
-        self.verify(ctx)?;
+        self.test_zig(ctx)?;
         Ok(())
     }
 }
@@ -194,26 +206,3 @@ fn arch() -> &'static str {
         &ARCH
     }
 }
-
-pub fn untar_xy(archive: &Path, dest: &Path) -> Result<()> {
-    let archive = archive
-        .to_str()
-        .ok_or(eyre::eyre!("Failed to read archive path"))?;
-    let dest = dest
-        .to_str()
-        .ok_or(eyre::eyre!("Failed to read destination path"))?;
-
-    let output = std::process::Command::new("tar")
-        .arg("-xf")
-        .arg(archive)
-        .arg("-C")
-        .arg(dest)
-        .output()?;
-
-    if !output.status.success() {
-        let err = String::from_utf8_lossy(&output.stderr);
-        return Err(eyre::eyre!("Failed to extract tar: {}", err));
-    }
-
-    Ok(())
-}
@@ -0,0 +1,362 @@
+use std::collections::HashSet;
+
+use eyre::Result;
+use serde_json::{json, Value};
+
+use crate::backend::{Backend, BackendType};
+use crate::git::Git;
+use crate::hash::hash_to_str;
+use crate::lockfile;
+use crate::toolset::{ToolRequest, ToolVersion, Toolset};
+
+/// a tool version in a software bill of materials
+#[derive(Debug, Clone, PartialEq)]
+pub struct SbomTool {
+    /// the tool as written in config files, e.g.: "node" or "npm:prettier"
+    pub id: String,
+    /// the name of the package in its backend, e.g.: "prettier"
+    pub name: String,
+    pub version: String,
+    pub backend: BackendType,
+    pub url: Option<String>,
+    pub sha256: Option<String>,
+    pub license: Option<String>,
+    pub plugin: Option<SbomPlugin>,
+}
+
+/// the asdf or vfox plugin a tool is installed with
+#[derive(Debug, Clone, PartialEq)]
+pub struct SbomPlugin {
+    pub name: String,
+    pub url: Option<String>,
+    /// the git sha the plugin is checked out at
+    pub revision: Option<String>,
+}
+
+/// collects what is known about the current versions in the toolset
+///
+/// Download urls and checksums come from the lockfile next to the config file that requested the
+/// tool so they are only known for locked tools.
+pub fn collect(ts: &Toolset) -> Result<Vec<SbomTool>> {
+    ts.list_current_versions()
+        .into_iter()
+        .filter(|(_, tv)| !matches!(tv.request, ToolRequest::System(_)))
+        .map(|(backend, tv)| collect_tool(ts, backend.as_ref(), &tv))
+        .collect()
+}
+
+fn collect_tool(ts: &Toolset, backend: &dyn Backend, tv: &ToolVersion) -> Result<SbomTool> {
+    let platform = match ts.versions.get(&tv.backend) {
+        Some(tvl) => lockfile::get_locked_platform(&tvl.source, tv)?,
+        None => None,
+    }
+    .unwrap_or_default();
+    let license = backend.license(tv).unwrap_or_else(|err| {
+        warn!("failed to fetch license of {tv}: {err:#}");
+        None
+    });
+    Ok(SbomTool {
+        id: tv.backend.short.clone(),
+        name: tv.backend.name.clone(),
+        version: tv.version.clone(),
+        backend: backend.get_type(),
+        url: platform.url,
+        sha256: platform.sha256,
+        license,
+        plugin: collect_plugin(backend),
+    })
+}
+
+fn collect_plugin(backend: &dyn Backend) -> Option<SbomPlugin> {
+    let ba = backend.fa();
+    let git = Git::new(backend.plugin_path()?);
+    let revision = match git.is_repo() {
+        true => git.current_sha().ok(),
+        false => None,
+    };
+    Some(SbomPlugin {
+        name: ba.short.clone(),
+        url: backend.get_remote_url().or_else(|| git.get_remote_url()),
+        revision,
+    })
+}
+
+/// https://github.com/package-url/purl-spec for the backends that install from a package
+/// registry
+fn purl(tool: &SbomTool) -> Option<String> {
+    let purl_type = match tool.backend {
+        BackendType::Cargo => "cargo",
+        BackendType::Conda => "conda",
+        BackendType::Dotnet => "nuget",
+        BackendType::Gem => "gem",
+        BackendType::Go => "golang",
+        BackendType::Npm => "npm",
+        BackendType::Pipx => "pypi",
+        _ => return None,
+    };
+    let name = match tool.backend {
+        // scoped packages, e.g.: "@antfu/ni"
+        BackendType::Npm => match tool.name.strip_prefix('@') {
+            Some(name) => format!("%40{name}"),
+            None => tool.name.clone(),
+        },
+        // module paths, e.g.: "github.com/golangci/golangci-lint/cmd/golangci-lint"
+        BackendType::Go => tool.name.clone(),
+        // installed from a git repo instead of the registry
+        _ if tool.name.contains(['/', ':']) => return None,
+        _ => tool.name.clone(),
+    };
+    Some(format!("pkg:{purl_type}/{name}@{}", tool.version))
+}
+
+/// a CycloneDX 1.5 JSON document, https://cyclonedx.org/docs/1.5/json/
+pub fn cyclonedx(tools: &[SbomTool], timestamp: &str) -> Value {
+    let mut components = vec![];
+    let mut dependencies = vec![];
+    let mut plugins = HashSet::new();
+    for tool in tools {
+        let bom_ref = format!("{}@{}", tool.id, tool.version);
+        let mut component = json!({
+            "type": "application",
+            "bom-ref": bom_ref,
+            "name": tool.name,
+            "version": tool.version,
+            "properties": [
+                {"name": "mise:tool", "value": tool.id},
+                {"name": "mise:backend", "value": tool.backend.to_string()},
+            ],
+        });
+        if let Some(purl) = purl(tool) {
+            component["purl"] = json!(purl);
+        }
+        if let Some(license) = &tool.license {
+            component["licenses"] = json!([{"expression": license}]);
+        }
+        if let Some(sha256) = &tool.sha256 {
+            component["hashes"] = json!([{"alg": "SHA-256", "content": sha256}]);
+        }
+        if let Some(url) = &tool.url {
+            component["externalReferences"] = json!([{"type": "distribution", "url": url}]);
+        }
+        components.push(component);
+        if let Some(plugin) = &tool.plugin {
+            let plugin_ref = format!("plugin:{}", plugin.name);
+            dependencies.push(json!({"ref": bom_ref, "dependsOn": [plugin_ref]}));
+            if !plugins.insert(plugin.name.clone()) {
+                continue;
+            }
+            let mut component = json!({
+                "type": "application",
+                "bom-ref": plugin_ref,
+                "name": plugin.name,
+                "properties": [
+                    {"name": "mise:plugin_type", "value": tool.backend.to_string()},
+                ],
+            });
+            if let Some(revision) = &plugin.revision {
+                component["version"] = json!(revision);
+            }
+            if let Some(url) = &plugin.url {
+                component["externalReferences"] = json!([{"type": "vcs", "url": url}]);
+            }
+            components.push(component);
+        }
+    }
+    json!({
+        "bomFormat": "CycloneDX",
+        "specVersion": "1.5",
+        "version": 1,
+        "metadata": {
+            "timestamp": timestamp,
+            "tools": {
+                "components": [
+                    {"type": "application", "name": "mise", "version": env!("CARGO_PKG_VERSION")},
+                ],
+            },
+        },
+        "components": components,
+        "dependencies": dependencies,
+    })
+}
+
+/// an SPDX 2.3 JSON document, https://spdx.github.io/spdx-spec/v2.3/
+pub fn spdx(tools: &[SbomTool], timestamp: &str) -> Value {
+    let mut packages = vec![];
+    let mut relationships = vec![];
+    let mut plugins = HashSet::new();
+    let noassertion = || "NOASSERTION".to_string();
+    for tool in tools {
+        let package_id = spdx_id(&format!("{}-{}", tool.id, tool.version));
+        let mut package = json!({
+            "name": tool.name,
+            "SPDXID": package_id,
+            "versionInfo": tool.version,
+            "downloadLocation": tool.url.clone().unwrap_or_else(noassertion),
+            "filesAnalyzed": false,
+            "licenseConcluded": "NOASSERTION",
+            "licenseDeclared": tool.license.clone().unwrap_or_else(noassertion),
+            "copyrightText": "NOASSERTION",
+            "comment": format!("{} from the {} backend", tool.id, tool.backend),
+        });
+        if let Some(sha256) = &tool.sha256 {
+            package["checksums"] = json!([{"algorithm": "SHA256", "checksumValue": sha256}]);
+        }
+        if let Some(purl) = purl(tool) {
+            package["externalRefs"] = json!([{
+                "referenceCategory": "PACKAGE-MANAGER",
+                "referenceType": "purl",
+                "referenceLocator": purl,
+            }]);
+        }
+        packages.push(package);
+        relationships.push(json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": package_id,
+        }));
+        if let Some(plugin) = &tool.plugin {
+            let plugin_id = spdx_id(&format!("plugin-{}", plugin.name));
+            relationships.push(json!({
+                "spdxElementId": package_id,
+                "relationshipType": "DEPENDS_ON",
+                "relatedSpdxElement": plugin_id,
+            }));
+            if !plugins.insert(plugin.name.clone()) {
+                continue;
+            }
+            // SPDX's VCS location syntax, e.g.: git+https://github.com/asdf-vm/asdf-nodejs@<sha>
+            let download_location = match (&plugin.url, &plugin.revision) {
+                (Some(url), Some(revision)) => format!("git+{url}@{revision}"),
+                (Some(url), None) => format!("git+{url}"),
+                (None, _) => noassertion(),
+            };
+            let mut package = json!({
+                "name": plugin.name,
+                "SPDXID": plugin_id,
+                "downloadLocation": download_location,
+                "filesAnalyzed": false,
+                "licenseConcluded": "NOASSERTION",
+                "licenseDeclared": "NOASSERTION",
+                "copyrightText": "NOASSERTION",
+                "comment": format!("{} plugin", tool.backend),
+            });
+            if let Some(revision) = &plugin.revision {
+                package["versionInfo"] = json!(revision);
+            }
+            packages.push(package);
+        }
+    }
+    let ids = tools.iter().map(|t| format!("{}@{}", t.id, t.version));
+    let namespace = hash_to_str(&(timestamp, ids.collect::<Vec<_>>()));
+    json!({
+        "spdxVersion": "SPDX-2.3",
+        "dataLicense": "CC0-1.0",
+        "SPDXID": "SPDXRef-DOCUMENT",
+        "name": "mise-toolset",
+        "documentNamespace": format!("https://mise.jdx.dev/spdx/{namespace}"),
+        "creationInfo": {
+            "created": timestamp,
+            "creators": [format!("Tool: mise-{}", env!("CARGO_PKG_VERSION"))],
+        },
+        "packages": packages,
+        "relationships": relationships,
+    })
+}
+
+/// SPDX ids can only contain letters, numbers, "." and "-"
+fn spdx_id(s: &str) -> String {
+    let s = s.replace(
+        |c: char| !c.is_ascii_alphanumeric() && c != '.' && c != '-',
+        "-",
+    );
+    format!("SPDXRef-{s}")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::backend;
+    use crate::cli::args::BackendArg;
+
+    use super::*;
+
+    fn tools() -> Vec<SbomTool> {
+        vec![
+            SbomTool {
+                id: "npm:@antfu/ni".into(),
+                name: "@antfu/ni".into(),
+                version: "0.21.12".into(),
+                backend: BackendType::Npm,
+                url: None,
+                sha256: None,
+                license: Some("MIT".into()),
+                plugin: None,
+            },
+            SbomTool {
+                id: "tiny".into(),
+                name: "tiny".into(),
+                version: "3.1.0".into(),
+                backend: BackendType::Asdf,
+                url: Some("https://example.com/tiny-3.1.0.tar.gz".into()),
+                sha256: Some("abc123".into()),
+                license: None,
+                plugin: Some(SbomPlugin {
+                    name: "tiny".into(),
+                    url: Some("https://github.com/mise-plugins/rtx-tiny".into()),
+                    revision: Some("def456".into()),
+                }),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_collect_plugin() {
+        let tiny = backend::get(&BackendArg::new("tiny", "tiny"));
+        assert_eq!(collect_plugin(tiny.as_ref()).unwrap().name, "tiny");
+        let ni = backend::get(&BackendArg::new("npm:@antfu/ni", "npm:@antfu/ni"));
+        assert_eq!(collect_plugin(ni.as_ref()), None);
+    }
+
+    #[test]
+    fn test_purl() {
+        let tools = tools();
+        assert_eq!(
+            purl(&tools[0]).as_deref(),
+            Some("pkg:npm/%40antfu/ni@0.21.12")
+        );
+        assert_eq!(purl(&tools[1]), None);
+    }
+
+    #[test]
+    fn test_cyclonedx() {
+        let bom = cyclonedx(&tools(), "2024-01-01T00:00:00Z");
+        assert_eq!(bom["bomFormat"], "CycloneDX");
+        let components = bom["components"].as_array().unwrap();
+        assert_eq!(components.len(), 3);
+        assert_eq!(components[0]["licenses"][0]["expression"], "MIT");
+        assert_eq!(components[1]["hashes"][0]["content"], "abc123");
+        assert_eq!(components[2]["bom-ref"], "plugin:tiny");
+        assert_eq!(components[2]["version"], "def456");
+        assert_eq!(bom["dependencies"][0]["dependsOn"][0], "plugin:tiny");
+    }
+
+    #[test]
+    fn test_spdx() {
+        let doc = spdx(&tools(), "2024-01-01T00:00:00Z");
+        let packages = doc["packages"].as_array().unwrap();
+        assert_eq!(packages.len(), 3);
+        assert_eq!(packages[0]["SPDXID"], "SPDXRef-npm--antfu-ni-0.21.12");
+        assert_eq!(packages[0]["licenseDeclared"], "MIT");
+        assert_eq!(packages[0]["downloadLocation"], "NOASSERTION");
+        assert_eq!(
+            packages[1]["downloadLocation"],
+            "https://example.com/tiny-3.1.0.tar.gz"
+        );
+        assert_eq!(
+            packages[2]["downloadLocation"],
+            "git+https://github.com/mise-plugins/rtx-tiny@def456"
+        );
+        assert_eq!(doc["relationships"].as_array().unwrap().len(), 3);
+    }
+}
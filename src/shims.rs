@@ -16,7 +16,7 @@ use crate::cli::exec::Exec;
 use crate::config::{Config, Settings};
 use crate::file::{create_dir_all, display_path, remove_all};
 use crate::lock_file::LockFile;
-use crate::toolset::{ToolVersion, Toolset, ToolsetBuilder};
+use crate::toolset::{tool_usage, ToolVersion, Toolset, ToolsetBuilder};
 use crate::{backend, dirs, env, fake_asdf, file, logger};
 
 // executes as if it was a shim if the command is not "mise", e.g.: "node"
@@ -52,6 +52,7 @@ fn which_shim(bin_name: &str) -> Result<PathBuf> {
                 "shim[{bin_name}] ToolVersion: {tv} bin: {bin}",
                 bin = display_path(&bin)
             );
+            tool_usage::record(&tv);
             return Ok(bin);
         }
     }
@@ -64,6 +65,7 @@ fn which_shim(bin_name: &str) -> Result<PathBuf> {
                     "shim[{bin_name}] NOT_FOUND ToolVersion: {tv} bin: {bin}",
                     bin = display_path(&bin)
                 );
+                tool_usage::record(&tv);
                 return Ok(bin);
             }
         }
@@ -18,6 +18,7 @@ pub use tool_request_set::{ToolRequestSet, ToolRequestSetBuilder};
 pub use tool_source::ToolSource;
 pub use tool_version::ToolVersion;
 pub use tool_version_list::ToolVersionList;
+pub use version_range::VersionRange;
 use versions::Version;
 
 use crate::backend::Backend;
@@ -29,14 +30,16 @@ use crate::errors::Error;
 use crate::install_context::InstallContext;
 use crate::path_env::PathEnv;
 use crate::ui::multi_progress_report::MultiProgressReport;
-use crate::{backend, env, runtime_symlinks, shims};
+use crate::{backend, env, lockfile, runtime_symlinks, shims};
 
 mod builder;
 mod tool_request;
 mod tool_request_set;
 mod tool_source;
+pub mod tool_usage;
 mod tool_version;
 mod tool_version_list;
+mod version_range;
 
 pub type ToolVersionOptions = BTreeMap<String, String>;
 
@@ -46,6 +49,7 @@ pub struct InstallOptions {
     pub jobs: Option<usize>,
     pub raw: bool,
     pub latest_versions: bool,
+    pub locked: bool,
 }
 
 impl InstallOptions {
@@ -211,7 +215,18 @@ impl Toolset {
                                         sleep(Duration::from_millis(100));
                                     }
                                 }
-                                let tv = tv.resolve(t.as_ref(), opts.latest_versions)?;
+                                let source = ts
+                                    .versions
+                                    .get(tv.backend())
+                                    .map(|tvl| tvl.source.clone())
+                                    .unwrap_or(ToolSource::Argument);
+                                let tv = lockfile::resolve(
+                                    t.as_ref(),
+                                    &source,
+                                    &tv,
+                                    opts.latest_versions,
+                                    opts.locked,
+                                )?;
                                 let ctx = InstallContext {
                                     ts,
                                     pr: mpr.add(&tv.style()),
@@ -340,6 +355,31 @@ impl Toolset {
             })
             .collect()
     }
+    /// like `list_outdated_versions` but compares against the latest version even if it doesn't
+    /// match the requested prefix. The last item is the request to bump to, e.g.: "22" for "20"
+    pub fn list_bumped_versions(&self) -> Vec<(Arc<dyn Backend>, ToolVersion, String, String)> {
+        self.list_current_versions()
+            .into_iter()
+            .filter_map(|(t, tv)| {
+                if t.symlink_path(&tv).is_some() {
+                    // do not consider symlinked versions to be outdated
+                    return None;
+                }
+                let latest =
+                    ToolRequest::new_opts(tv.backend.clone(), "latest", tv.request.options())
+                        .and_then(|tvr| tvr.resolve(t.as_ref(), true));
+                let latest = match latest {
+                    Ok(latest) => latest.version,
+                    Err(e) => {
+                        warn!("Error getting latest version for {t}: {e:#}");
+                        return None;
+                    }
+                };
+                let bump = bump_request(&tv.request.version(), &latest)?;
+                Some((t, tv, latest, bump))
+            })
+            .collect()
+    }
     pub fn full_env(&self) -> Result<BTreeMap<String, String>> {
         let mut env = env::PRISTINE_ENV
             .clone()
@@ -558,13 +598,30 @@ fn is_outdated_version(current: &str, latest: &str) -> bool {
     current != latest
 }
 
+/// the request that upgrades `requested` to `latest` with the same precision, e.g.: "22" for
+/// "20" and "22.1.0" or "3.12" for "3.11" and "3.12.4". Only numeric requests can be bumped.
+fn bump_request(requested: &str, latest: &str) -> Option<String> {
+    let numeric = regex!(r"^\d+(\.\d+)*$");
+    if !numeric.is_match(requested) {
+        return None;
+    }
+    let bump = latest
+        .split('.')
+        .take(requested.split('.').count())
+        .join(".");
+    if !numeric.is_match(&bump) || !is_outdated_version(requested, &bump) {
+        return None;
+    }
+    Some(bump)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::backend::reset;
     use pretty_assertions::assert_eq;
     use test_log::test;
 
-    use super::is_outdated_version;
+    use super::{bump_request, is_outdated_version};
 
     #[test]
     fn test_is_outdated_version() {
@@ -591,4 +648,16 @@ mod tests {
             false
         );
     }
+
+    #[test]
+    fn test_bump_request() {
+        let bump = |requested, latest| bump_request(requested, latest);
+        assert_eq!(bump("20", "22.1.0"), Some("22".to_string()));
+        assert_eq!(bump("3.11", "3.12.4"), Some("3.12".to_string()));
+        assert_eq!(bump("20.1.0", "22.1.0"), Some("22.1.0".to_string()));
+        assert_eq!(bump("22", "22.1.0"), None);
+        assert_eq!(bump("3.12", "3.11.9"), None);
+        assert_eq!(bump("latest", "22.1.0"), None);
+        assert_eq!(bump("temurin-17", "temurin-21.0.1"), None);
+    }
 }
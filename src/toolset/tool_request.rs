@@ -8,7 +8,7 @@ use xx::file;
 use crate::backend;
 use crate::backend::Backend;
 use crate::cli::args::BackendArg;
-use crate::toolset::{ToolVersion, ToolVersionOptions};
+use crate::toolset::{ToolVersion, ToolVersionOptions, VersionRange};
 
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum ToolRequest {
@@ -22,6 +22,11 @@ pub enum ToolRequest {
         prefix: String,
         options: ToolVersionOptions,
     },
+    Range {
+        backend: BackendArg,
+        range: VersionRange,
+        options: ToolVersionOptions,
+    },
     Ref {
         backend: BackendArg,
         ref_: String,
@@ -64,6 +69,12 @@ impl ToolRequest {
             None => {
                 if s == "system" {
                     Self::System(backend)
+                } else if VersionRange::is_range(&s) {
+                    Self::Range {
+                        backend,
+                        range: VersionRange::parse(&s)?,
+                        options: Default::default(),
+                    }
                 } else {
                     Self::Version {
                         backend,
@@ -84,6 +95,7 @@ impl ToolRequest {
         match &mut tvr {
             Self::Version { options: o, .. }
             | Self::Prefix { options: o, .. }
+            | Self::Range { options: o, .. }
             | Self::Ref { options: o, .. } => *o = options,
             _ => Default::default(),
         }
@@ -93,6 +105,7 @@ impl ToolRequest {
         match self {
             Self::Version { backend: f, .. }
             | Self::Prefix { backend: f, .. }
+            | Self::Range { backend: f, .. }
             | Self::Ref { backend: f, .. }
             | Self::Path(f, _)
             | Self::Sub { backend: f, .. }
@@ -107,6 +120,7 @@ impl ToolRequest {
         match self {
             Self::Version { version: v, .. } => v.clone(),
             Self::Prefix { prefix: p, .. } => format!("prefix:{p}"),
+            Self::Range { range, .. } => range.to_string(),
             Self::Ref {
                 ref_: r, ref_type, ..
             } => format!("{ref_type}:{r}"),
@@ -122,6 +136,7 @@ impl ToolRequest {
         match self {
             Self::Version { options: o, .. }
             | Self::Prefix { options: o, .. }
+            | Self::Range { options: o, .. }
             | Self::Ref { options: o, .. } => o.clone(),
             _ => Default::default(),
         }
@@ -164,6 +179,11 @@ impl ToolRequest {
                     .cloned(),
                 Err(_) => None,
             },
+            Self::Range { backend, range, .. } => self
+                .local_resolve(&range.to_string())
+                .inspect_err(|e| warn!("ToolRequest.local_resolve: {e:#}"))
+                .unwrap_or_default()
+                .map(|v| backend.installs_path.join(v)),
             Self::Path(_, path) => Some(path.clone()),
             Self::System(_) => None,
         }
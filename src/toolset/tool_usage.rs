@@ -0,0 +1,97 @@
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use eyre::Result;
+use filetime::{set_file_mtime, FileTime};
+
+use crate::dirs::{INSTALLS, TOOL_USAGE};
+use crate::duration::HOURLY;
+use crate::file;
+use crate::hash::hash_to_str;
+use crate::toolset::{ToolVersion, Toolset};
+
+/// records that `tv` was just used by a shim, `mise exec` or `hook-env`
+///
+/// Each installed version has an empty file in ~/.local/state/mise/tool-usage whose mtime is
+/// the last time it was used. Failures are only logged since this runs before every shim.
+pub fn record(tv: &ToolVersion) {
+    if let Err(err) = touch(tv) {
+        debug!("failed to record usage of {tv}: {err:#}");
+    }
+}
+
+/// records all of the installed versions in the toolset as used
+pub fn record_toolset(ts: &Toolset) {
+    for (_, tv) in ts.list_current_installed_versions() {
+        record(&tv);
+    }
+}
+
+/// when `tv` was last used, falls back to when it was installed if its use was never recorded
+pub fn last_used(tv: &ToolVersion) -> Option<SystemTime> {
+    let path = usage_path(tv);
+    let path = match path.exists() {
+        true => path,
+        false => tv.install_path(),
+    };
+    path.metadata().and_then(|m| m.modified()).ok()
+}
+
+/// true if `tv` hasn't been used in `duration`
+pub fn is_unused_for(tv: &ToolVersion, duration: Duration) -> bool {
+    last_used(tv)
+        .and_then(|t| t.elapsed().ok())
+        .is_some_and(|elapsed| elapsed > duration)
+}
+
+pub fn remove(tv: &ToolVersion) -> Result<()> {
+    let path = usage_path(tv);
+    if path.exists() {
+        file::remove_file(path)?;
+    }
+    Ok(())
+}
+
+fn touch(tv: &ToolVersion) -> Result<()> {
+    let path = usage_path(tv);
+    match path.metadata().and_then(|m| m.modified()) {
+        // only written once an hour so recording is usually just a stat call
+        Ok(modified) if modified.elapsed().unwrap_or_default() < HOURLY => {}
+        Ok(_) => set_file_mtime(&path, FileTime::now())?,
+        Err(_) => {
+            file::create_dir_all(path.parent().unwrap())?;
+            file::write(&path, "")?;
+        }
+    }
+    Ok(())
+}
+
+fn usage_path(tv: &ToolVersion) -> PathBuf {
+    let install_path = tv.install_path();
+    match install_path.strip_prefix(*INSTALLS) {
+        Ok(relative) => TOOL_USAGE.join(relative),
+        Err(_) => TOOL_USAGE.join(hash_to_str(&install_path)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::backend;
+    use crate::cli::args::BackendArg;
+    use crate::toolset::ToolRequest;
+
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let ba = BackendArg::new("tiny", "tiny");
+        let tool = backend::get(&ba);
+        let tvr = ToolRequest::new(ba, "3.1.0").unwrap();
+        let tv = ToolVersion::new(tool.as_ref(), tvr, "3.1.0".into());
+        remove(&tv).unwrap();
+        record(&tv);
+        assert!(!is_unused_for(&tv, Duration::from_secs(60)));
+        remove(&tv).unwrap();
+        assert!(!usage_path(&tv).exists());
+    }
+}
@@ -3,14 +3,16 @@ use std::fmt::{Display, Formatter};
 use std::fs;
 use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
+use std::time::Duration;
 
+use chrono::{DateTime, NaiveDate, Utc};
 use console::style;
-use eyre::Result;
+use eyre::{bail, Result, WrapErr};
 
 use crate::backend;
 use crate::backend::{ABackend, Backend};
 use crate::cli::args::BackendArg;
-use crate::config::Config;
+use crate::config::{Config, Settings};
 #[cfg(windows)]
 use crate::file;
 use crate::hash::hash_to_str;
@@ -22,6 +24,9 @@ pub struct ToolVersion {
     pub request: ToolRequest,
     pub backend: BackendArg,
     pub version: String,
+    /// set while the version is being installed, `install_path` then points to a staging dir
+    /// that is renamed to the real install path once the install succeeds
+    pub staged: bool,
 }
 
 impl ToolVersion {
@@ -30,6 +35,7 @@ impl ToolVersion {
             backend: tool.fa().clone(),
             version,
             request,
+            staged: false,
         }
     }
 
@@ -49,6 +55,9 @@ impl ToolVersion {
                 Self::resolve_version(backend, request, latest_versions, &v)?
             }
             ToolRequest::Prefix { prefix, .. } => Self::resolve_prefix(backend, request, &prefix)?,
+            ToolRequest::Range { range, .. } => {
+                Self::resolve_range(backend, request, latest_versions, &range.to_string())?
+            }
             ToolRequest::Sub {
                 sub, orig_version, ..
             } => Self::resolve_sub(backend, request, latest_versions, &sub, &orig_version)?,
@@ -65,6 +74,9 @@ impl ToolVersion {
     }
 
     pub fn install_path(&self) -> PathBuf {
+        if self.staged {
+            return self.staging_path();
+        }
         let pathname = match &self.request {
             ToolRequest::Path(_, p) => p.to_string_lossy().to_string(),
             _ => self.tv_pathname(),
@@ -85,7 +97,7 @@ impl ToolVersion {
         path
     }
     pub fn install_short_path(&self) -> PathBuf {
-        if cfg!(windows) {
+        if cfg!(windows) || self.staged {
             return self.install_path();
         }
         let pathname = match &self.request {
@@ -99,6 +111,14 @@ impl ToolVersion {
             self.install_path()
         }
     }
+    /// e.g.: ~/.local/share/mise/installs/node/.20.11.1.staging
+    /// it starts with a dot so `list_installed_versions` skips it
+    pub fn staging_path(&self) -> PathBuf {
+        let pathname = self.tv_pathname();
+        self.backend
+            .installs_path
+            .join(format!(".{pathname}.staging"))
+    }
     pub fn cache_path(&self) -> PathBuf {
         self.backend.cache_path.join(self.tv_pathname())
     }
@@ -120,6 +140,7 @@ impl ToolVersion {
         match &self.request {
             ToolRequest::Version { .. } => self.version.to_string(),
             ToolRequest::Prefix { .. } => self.version.to_string(),
+            ToolRequest::Range { .. } => self.version.to_string(),
             ToolRequest::Sub { .. } => self.version.to_string(),
             ToolRequest::Ref { ref_: r, .. } => format!("ref-{}", r),
             ToolRequest::Path(_, p) => format!("path-{}", hash_to_str(p)),
@@ -142,6 +163,8 @@ impl ToolVersion {
         v: &str,
     ) -> Result<ToolVersion> {
         let config = Config::get();
+        // without network access only installed versions can be used for "latest"
+        let latest_versions = latest_versions && !Settings::get().offline;
         let v = config.resolve_alias(backend, v)?;
         match v.split_once(':') {
             Some((ref_type @ ("ref" | "tag" | "branch" | "rev"), r)) => {
@@ -185,7 +208,7 @@ impl ToolVersion {
                     return build(v);
                 }
             }
-            if let Some(v) = backend.latest_version(None)? {
+            if let Some(v) = Self::latest_remote_version(backend, &request, None)? {
                 return build(v);
             }
         }
@@ -214,7 +237,10 @@ impl ToolVersion {
         v: &str,
     ) -> Result<Self> {
         let v = match v {
-            "latest" => tool.latest_version(None)?.unwrap(),
+            "latest" => match Self::latest_remote_version(tool, &request, None)? {
+                Some(v) => v,
+                None => bail!("no versions found for {}", tool.id()),
+            },
             _ => Config::get().resolve_alias(tool, v)?,
         };
         let v = tool_request::version_sub(&v, sub);
@@ -223,12 +249,95 @@ impl ToolVersion {
 
     fn resolve_prefix(tool: &dyn Backend, request: ToolRequest, prefix: &str) -> Result<Self> {
         let matches = tool.list_versions_matching(prefix)?;
-        let v = match matches.last() {
+        let v = match Self::latest_released(tool, &request, prefix, matches)? {
             Some(v) => v,
-            None => prefix,
+            None => prefix.to_string(),
             // None => Err(VersionNotFound(plugin.name.clone(), prefix.to_string()))?,
         };
-        Ok(Self::new(tool, request, v.to_string()))
+        Ok(Self::new(tool, request, v))
+    }
+
+    /// resolves a range like ">=3.10,<3.13" to the highest installed or available version that
+    /// satisfies it
+    fn resolve_range(
+        tool: &dyn Backend,
+        request: ToolRequest,
+        latest_versions: bool,
+        range: &str,
+    ) -> Result<Self> {
+        let latest_versions = latest_versions && !Settings::get().offline;
+        if !latest_versions {
+            if let Some(v) = tool.latest_installed_version(Some(range.to_string()))? {
+                return Ok(Self::new(tool, request, v));
+            }
+        }
+        match Self::latest_remote_version(tool, &request, Some(range.to_string()))? {
+            Some(v) => Ok(Self::new(tool, request, v)),
+            None => bail!("no version of {} matches {range}", tool.id()),
+        }
+    }
+
+    /// like `Backend::latest_version` but skips versions newer than `minimum_release_age`, errors
+    /// if that skips every matching version
+    fn latest_remote_version(
+        tool: &dyn Backend,
+        request: &ToolRequest,
+        query: Option<String>,
+    ) -> Result<Option<String>> {
+        if minimum_release_age(request)?.is_none() {
+            return tool.latest_version(query);
+        }
+        let query = query.unwrap_or_else(|| "latest".to_string());
+        let matches = tool.list_versions_matching(&query)?;
+        Self::latest_released(tool, request, &query, matches)
+    }
+
+    /// the last of `matches` that is older than `minimum_release_age`. Errors if every match is
+    /// too new so the version isn't silently resolved to something else.
+    fn latest_released(
+        tool: &dyn Backend,
+        request: &ToolRequest,
+        query: &str,
+        matches: Vec<String>,
+    ) -> Result<Option<String>> {
+        if matches.is_empty() {
+            return Ok(None);
+        }
+        let matches = Self::filter_release_age(tool, request, matches)?;
+        match matches.last() {
+            Some(v) => Ok(Some(v.clone())),
+            None => bail!(
+                "no version of {}@{query} is older than minimum_release_age ({})",
+                tool.id(),
+                humantime::format_duration(minimum_release_age(request)?.unwrap_or_default())
+            ),
+        }
+    }
+
+    /// removes versions that were published less than `minimum_release_age` ago
+    fn filter_release_age(
+        tool: &dyn Backend,
+        request: &ToolRequest,
+        versions: Vec<String>,
+    ) -> Result<Vec<String>> {
+        let Some(min_age) = minimum_release_age(request)? else {
+            return Ok(versions);
+        };
+        let cutoff = Utc::now() - min_age;
+        let dates = tool.list_remote_release_dates()?;
+        Ok(versions
+            .into_iter()
+            .filter(|v| match dates.get(v).and_then(|d| parse_release_date(d)) {
+                Some(date) if date > cutoff => {
+                    debug!(
+                        "skipping {}@{v} released at {date}, it is newer than minimum_release_age",
+                        tool.id()
+                    );
+                    false
+                }
+                _ => true,
+            })
+            .collect())
     }
 
     fn resolve_ref(
@@ -255,6 +364,29 @@ impl ToolVersion {
     }
 }
 
+/// the `minimum_release_age` tool option, e.g.:
+/// `node = { version = "20", minimum_release_age = "7d" }`, or setting
+fn minimum_release_age(request: &ToolRequest) -> Result<Option<Duration>> {
+    let age = match request.options().get("minimum_release_age") {
+        Some(age) => Some(age.clone()),
+        None => Settings::get().minimum_release_age.clone(),
+    };
+    age.map(|age| {
+        humantime::parse_duration(&age)
+            .wrap_err_with(|| format!("invalid minimum_release_age: {age}"))
+    })
+    .transpose()
+}
+
+/// parses "2024-01-09T16:12:40.123Z" or "2024-01-09"
+fn parse_release_date(date: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(date) {
+        return Some(date.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    Some(date.and_hms_opt(0, 0, 0)?.and_utc())
+}
+
 impl Display for ToolVersion {
     fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         write!(f, "{}@{}", &self.backend.full, &self.version)
@@ -290,3 +422,85 @@ impl Hash for ToolVersion {
         self.version.hash(state);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use crate::backend::version_info::VersionInfo;
+    use crate::install_context::InstallContext;
+    use crate::test::reset;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_release_date() {
+        let date = |s| parse_release_date(s).map(|d| d.to_rfc3339());
+        assert_eq!(
+            date("2024-01-09T16:12:40.123Z").unwrap(),
+            "2024-01-09T16:12:40.123+00:00"
+        );
+        assert_eq!(
+            date("2024-01-09T18:12:40+02:00").unwrap(),
+            "2024-01-09T16:12:40+00:00"
+        );
+        assert_eq!(date("2024-01-09").unwrap(), "2024-01-09T00:00:00+00:00");
+        assert_eq!(date("yesterday"), None);
+    }
+
+    #[test]
+    fn test_staged_install_path() {
+        let ba = BackendArg::new("tiny", "tiny");
+        let tool = backend::get(&ba);
+        let tvr = ToolRequest::new(ba, "3.1.0").unwrap();
+        let mut tv = ToolVersion::new(tool.as_ref(), tvr, "3.1.0".into());
+        assert!(tv.install_path().ends_with("tiny/3.1.0"));
+        assert!(tv.staging_path().ends_with("tiny/.3.1.0.staging"));
+        tv.staged = true;
+        assert_eq!(tv.install_path(), tv.staging_path());
+        assert_eq!(tv.install_short_path(), tv.staging_path());
+    }
+
+    /// a backend with fixed versions that were all released at the start of 2024
+    #[derive(Debug)]
+    struct ReleasedBackend(BackendArg);
+
+    impl Backend for ReleasedBackend {
+        fn fa(&self) -> &BackendArg {
+            &self.0
+        }
+        fn _list_remote_versions(&self) -> Result<Vec<String>> {
+            Ok(vec!["20.0.0".into(), "20.1.0".into(), "21.0.0".into()])
+        }
+        fn _list_remote_versions_info(&self) -> Result<Vec<VersionInfo>> {
+            Ok(self
+                ._list_remote_versions()?
+                .into_iter()
+                .map(|v| VersionInfo {
+                    release_date: Some("2024-01-01".into()),
+                    ..VersionInfo::new(v)
+                })
+                .collect())
+        }
+        fn install_version_impl(&self, _ctx: &InstallContext) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_resolve_prefix_minimum_release_age() {
+        reset();
+        let ba = BackendArg::new("released", "released");
+        let tool = ReleasedBackend(ba.clone());
+        let resolve = |age: &str| {
+            let opts = [("minimum_release_age".to_string(), age.to_string())].into();
+            let tvr = ToolRequest::new_opts(ba.clone(), "20", opts).unwrap();
+            ToolVersion::resolve_prefix(&tool, tvr, "20").map(|tv| tv.version)
+        };
+        assert_eq!(resolve("1d").unwrap(), "20.1.0");
+        let err = resolve("100years").unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("no version of released@20 is older than minimum_release_age"));
+    }
+}
@@ -1,8 +1,8 @@
-use crate::backend;
 use crate::cli::args::BackendArg;
 use crate::errors::Error;
 use crate::toolset::tool_request::ToolRequest;
 use crate::toolset::{ToolSource, ToolVersion};
+use crate::{backend, lockfile};
 
 /// represents several versions of a tool for a particular plugin
 #[derive(Debug, Clone)]
@@ -26,7 +26,7 @@ impl ToolVersionList {
         self.versions.clear();
         let plugin = backend::get(&self.backend);
         for tvr in &mut self.requests {
-            match tvr.resolve(plugin.as_ref(), latest_versions) {
+            match lockfile::resolve(plugin.as_ref(), &self.source, tvr, latest_versions, false) {
                 Ok(v) => self.versions.push(v),
                 Err(err) => {
                     return Err(Error::FailedToResolveVersion {
@@ -0,0 +1,255 @@
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+
+use eyre::{bail, Result};
+use itertools::Itertools;
+
+/// a version constraint in npm/cargo range syntax, e.g.: ">=3.10,<3.13", "^20.11" or
+/// "1.2 - 1.4 || >=2"
+///
+/// Comparators in a set can be separated by spaces or commas and all of them have to match.
+/// Sets are separated by "||" and any of them can match. Only versions made of numbers like
+/// "3.12.1" or "v20.11.0" can match a range, prereleases and other versions never do.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct VersionRange {
+    sets: Vec<Vec<Comparator>>,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct Comparator {
+    /// how the comparator was written so the range can be displayed like the user wrote it
+    raw: String,
+    bounds: Vec<Bound>,
+}
+
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+enum Bound {
+    /// version >= parts
+    Min(Vec<u64>),
+    /// version < parts
+    Max(Vec<u64>),
+}
+
+impl VersionRange {
+    /// true if `s` is meant as a range rather than a version or prefix
+    pub fn is_range(s: &str) -> bool {
+        s.starts_with(['<', '>', '=', '^', '~', '*'])
+            || s.contains("||")
+            || s.contains(',')
+            || s.contains(" - ")
+            || s.split(['.', ' '])
+                .any(|p| p == "x" || p == "X" || p == "*")
+    }
+
+    pub fn parse(s: &str) -> Result<Self> {
+        let sets = s.split("||").map(parse_set).collect::<Result<Vec<_>>>()?;
+        Ok(Self { sets })
+    }
+
+    pub fn matches(&self, version: &str) -> bool {
+        let Some(version) = parse_version(version) else {
+            return false;
+        };
+        self.sets.iter().any(|set| {
+            set.iter().flat_map(|c| &c.bounds).all(|bound| match bound {
+                Bound::Min(min) => compare(&version, min) != Ordering::Less,
+                Bound::Max(max) => compare(&version, max) == Ordering::Less,
+            })
+        })
+    }
+}
+
+impl Display for VersionRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sets = self
+            .sets
+            .iter()
+            .map(|set| set.iter().map(|c| &c.raw).join(","))
+            .join("||");
+        write!(f, "{sets}")
+    }
+}
+
+fn parse_set(s: &str) -> Result<Vec<Comparator>> {
+    let mut tokens = vec![];
+    let mut pending_op = None;
+    for token in s.split([' ', ',']).filter(|t| !t.is_empty()) {
+        if matches!(token, "<" | "<=" | ">" | ">=" | "=" | "^" | "~") {
+            // allow a space between the operator and the version, e.g.: ">= 3.10"
+            pending_op = Some(token);
+            continue;
+        }
+        match pending_op.take() {
+            Some(op) => tokens.push(format!("{op}{token}")),
+            None => tokens.push(token.to_string()),
+        }
+    }
+    if let Some(op) = pending_op {
+        bail!("invalid version range: {s}, {op} is missing a version");
+    }
+    let mut comparators = vec![];
+    let mut tokens = tokens.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        if tokens.peek().is_some_and(|t| t == "-") {
+            // hyphen range, e.g.: "1.2 - 1.4"
+            tokens.next();
+            let Some(to) = tokens.next() else {
+                bail!("invalid version range: {s}, hyphen range is missing an upper bound");
+            };
+            comparators.push(parse_comparator(&format!(">={token}"))?);
+            comparators.push(parse_comparator(&format!("<={to}"))?);
+        } else {
+            comparators.push(parse_comparator(&token)?);
+        }
+    }
+    if comparators.is_empty() {
+        bail!("invalid version range: {s}");
+    }
+    Ok(comparators)
+}
+
+fn parse_comparator(s: &str) -> Result<Comparator> {
+    let i = s.find(|c: char| !"<>=^~".contains(c)).unwrap_or(s.len());
+    let (op, v) = s.split_at(i);
+    let parts = parse_partial(v).ok_or_else(|| eyre::eyre!("invalid version in range: {s}"))?;
+    let bounds = match (op, parts.as_slice()) {
+        (_, []) => vec![],
+        (">=", p) => vec![Bound::Min(p.to_vec())],
+        (">", p) => vec![Bound::Min(bump(p, p.len() - 1))],
+        ("<", p) => vec![Bound::Max(p.to_vec())],
+        ("<=", p) => vec![Bound::Max(bump(p, p.len() - 1))],
+        ("" | "=", p) => vec![Bound::Min(p.to_vec()), Bound::Max(bump(p, p.len() - 1))],
+        ("^", p) => {
+            // the first non-zero part can't change
+            let i = p.iter().position(|n| *n != 0).unwrap_or(p.len() - 1);
+            vec![Bound::Min(p.to_vec()), Bound::Max(bump(p, i))]
+        }
+        ("~", p) => vec![
+            Bound::Min(p.to_vec()),
+            Bound::Max(bump(p, 1.min(p.len() - 1))),
+        ],
+        _ => bail!("invalid operator in version range: {s}"),
+    };
+    Ok(Comparator {
+        raw: s.to_string(),
+        bounds,
+    })
+}
+
+/// parses a version that may be missing parts, e.g.: "3.10", "1.2.x" or "*"
+fn parse_partial(s: &str) -> Option<Vec<u64>> {
+    let s = s.trim_start_matches('v');
+    let mut parts = vec![];
+    for part in s.split('.') {
+        match part {
+            "x" | "X" | "*" => return Some(parts),
+            _ => parts.push(part.parse().ok()?),
+        }
+    }
+    Some(parts)
+}
+
+/// parses a full version like "3.12.1" or "v20.11.0"
+fn parse_version(s: &str) -> Option<Vec<u64>> {
+    s.trim_start_matches('v')
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect()
+}
+
+/// the lowest version that doesn't start with the first `i + 1` parts, e.g.: bump([1, 2, 3], 1) is
+/// [1, 3]
+fn bump(parts: &[u64], i: usize) -> Vec<u64> {
+    let mut parts = parts[..=i].to_vec();
+    parts[i] += 1;
+    parts
+}
+
+/// compares versions with missing parts treated as 0
+fn compare(a: &[u64], b: &[u64]) -> Ordering {
+    let len = a.len().max(b.len());
+    let get = |v: &[u64], i: usize| v.get(i).copied().unwrap_or_default();
+    (0..len)
+        .map(|i| get(a, i).cmp(&get(b, i)))
+        .find(|o| *o != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn matching(range: &str, versions: &[&str]) -> Vec<String> {
+        let range = VersionRange::parse(range).unwrap();
+        versions
+            .iter()
+            .filter(|v| range.matches(v))
+            .map(|v| v.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_is_range() {
+        assert!(VersionRange::is_range(">=3.10,<3.13"));
+        assert!(VersionRange::is_range("^20.11"));
+        assert!(VersionRange::is_range("~1.2"));
+        assert!(VersionRange::is_range("1.2.x"));
+        assert!(VersionRange::is_range("1.2 - 1.4"));
+        assert!(VersionRange::is_range("18 || 20"));
+        assert!(!VersionRange::is_range("20"));
+        assert!(!VersionRange::is_range("3.12.1"));
+        assert!(!VersionRange::is_range("latest"));
+        assert!(!VersionRange::is_range("temurin-17"));
+    }
+
+    #[test]
+    fn test_matches() {
+        let versions = [
+            "3.9.18",
+            "3.10.0",
+            "3.12.1",
+            "3.13.0",
+            "3.13.0rc1",
+            "20.10.0",
+            "20.11.0",
+            "20.11.1",
+            "21.0.0",
+        ];
+        assert_eq!(
+            matching(">=3.10,<3.13", &versions),
+            vec!["3.10.0", "3.12.1"]
+        );
+        assert_eq!(
+            matching(">= 3.10 < 3.13", &versions),
+            vec!["3.10.0", "3.12.1"]
+        );
+        assert_eq!(matching("^20.11", &versions), vec!["20.11.0", "20.11.1"]);
+        assert_eq!(matching("~3.12", &versions), vec!["3.12.1"]);
+        assert_eq!(matching(">3.12", &versions)[0], "3.13.0");
+        assert_eq!(matching("<=3.10", &versions), vec!["3.9.18", "3.10.0"]);
+        assert_eq!(matching("3.9.x || 21", &versions), vec!["3.9.18", "21.0.0"]);
+        assert_eq!(matching("3.10 - 3.12", &versions), vec!["3.10.0", "3.12.1"]);
+        assert_eq!(matching("*", &versions).len(), 8);
+        assert_eq!(
+            matching("^0.2.3", &["0.2.3", "0.2.9", "0.3.0"]),
+            vec!["0.2.3", "0.2.9"]
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        for (range, display) in [
+            (">=3.10,<3.13", ">=3.10,<3.13"),
+            (">= 3.10, <3.13", ">=3.10,<3.13"),
+            ("^20.11", "^20.11"),
+            ("1.2 - 1.4 || 2.x", ">=1.2,<=1.4||2.x"),
+        ] {
+            assert_eq!(VersionRange::parse(range).unwrap().to_string(), display);
+        }
+        assert!(VersionRange::parse(">=").is_err());
+        assert!(VersionRange::parse(">=abc").is_err());
+        assert!(VersionRange::parse("1.2 -").is_err());
+    }
+}
@@ -11,6 +11,7 @@ pub trait SingleReport: Send + Sync {
     fn set_message(&self, _message: String) {}
     fn inc(&self, _delta: u64) {}
     fn set_length(&self, _length: u64) {}
+    fn set_position(&self, _position: u64) {}
     fn finish(&self) {}
     fn finish_with_message(&self, _message: String) {}
 }
@@ -24,9 +25,9 @@ static PROG_TEMPLATE: Lazy<ProgressStyle> = Lazy::new(|| {
     let tmpl = match *env::TERM_WIDTH {
         0..=89 => "{prefix} {wide_msg} {bar:10.cyan/blue} {percent:>2}%",
         90..=99 => "{prefix} {wide_msg} {bar:15.cyan/blue} {percent:>2}%",
-        100..=114 => "{prefix} {wide_msg} {bytes}/{total_bytes:10} {bar:10.cyan/blue}",
+        100..=114 => "{prefix} {wide_msg} {bytes}/{total_bytes:10} ({eta}) {bar:10.cyan/blue}",
         _ => {
-            "{prefix} {wide_msg} {bytes}/{total_bytes} ({eta}) {bar:20.cyan/blue} {elapsed:>3.dim.italic}"
+            "{prefix} {wide_msg} {bytes}/{total_bytes} {bytes_per_sec} ({eta}) {bar:20.cyan/blue} {elapsed:>3.dim.italic}"
         }
     };
     ProgressStyle::with_template(tmpl).unwrap()
@@ -107,6 +108,11 @@ impl SingleReport for ProgressReport {
         self.pb.disable_steady_tick();
         self.pb.set_length(length);
     }
+    fn set_position(&self, position: u64) {
+        // the bytes of a resumed download shouldn't count towards the speed and eta
+        self.pb.set_position(position);
+        self.pb.reset_eta();
+    }
     fn finish(&self) {
         self.pb.set_style(SUCCESS_TEMPLATE.clone());
         self.pb